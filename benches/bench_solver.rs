@@ -1,12 +1,12 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use rcuber::{cubie::CubieCube, moves::Formula, solver::{CFOPSolver, LBLSolver, Min2PhaseSolver}};
+use rcuber::{cubie::CubieCube, moves::Formula, solver::{CFOPSolver, LBLSolver, Min2PhaseSolver, Solver}};
 
 fn lbl() {
     let cc = CubieCube::default();
     let moves = Formula::scramble();
     // println!("Scramble: {:?}", moves);
     let cc = cc.apply_formula(&moves);
-    let mut solver = LBLSolver { cube: cc };
+    let mut solver = LBLSolver::new(cc);
     let _s = solver.solve();
     assert!(solver.is_solved());
 }
@@ -16,7 +16,7 @@ fn cfop() {
     let moves = Formula::scramble();
     // println!("Scramble: {:?}", moves);
     let cc = cc.apply_formula(&moves);
-    let mut solver = CFOPSolver { cube: cc };
+    let mut solver = CFOPSolver::new(cc);
     let _s = solver.solve();
     assert!(solver.is_solved());
 }
@@ -25,7 +25,7 @@ fn m2p() {
     let moves = Formula::scramble();
     // println!("Scramble: {:?}", moves);
     let cc = cc.apply_formula(&moves);
-    let mut solver = Min2PhaseSolver { cube: cc };
+    let mut solver = Min2PhaseSolver::new(cc);
     let _s = solver.solve();
     assert!(solver.is_solved());
 }
@@ -0,0 +1,302 @@
+//! # Alg
+//! `Alg` is an algorithm kept as a tree, unlike `Formula` which is a flat list of moves.
+//! * Sequence: `R U R' U'`
+//! * Group with repeat: `(R U R' U')3`
+//! * Commutator: `[A, B]` = `A B A' B'`
+//! * Conjugate: `[A: B]` = `A B A'`
+//! * Pause: `.`
+//!
+//! Comments (`//` to the end of line) are ignored when parsing.
+//! # Example
+//! ```rust
+//! use rcuber::alg::Alg;
+//! use rcuber::moves::Formula;
+//!
+//! fn main() {
+//!     let alg = Alg::from_string("[R U R', D]").unwrap();
+//!     assert_eq!(alg.to_string(), "[R U R', D]");
+//!     assert_eq!(alg.invert().to_string(), "[D, R U R']");
+//!     let formula = alg.to_formula();
+//!     assert_eq!(formula.moves, Formula::from_string("R U R' D R U' R' D'").unwrap().moves);
+//! }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::moves::{Formula, Move, Parser, Plane, MAX_MOVES};
+
+/// Node of an algorithm tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Alg {
+    /// A single move.
+    Move(Move),
+    /// Algs one after another.
+    Sequence(Vec<Alg>),
+    /// An alg in parentheses, repeated.
+    Group(Box<Alg>, usize),
+    /// `[A, B]` = `A B A' B'`.
+    Commutator(Box<Alg>, Box<Alg>),
+    /// `[A: B]` = `A B A'`.
+    Conjugate(Box<Alg>, Box<Alg>),
+    /// A pause, `.`, doesn't move the cube.
+    Pause,
+}
+
+impl Alg {
+    /// Parse an alg, eg. "[F: [R, U]]", "(R U R' U')3 . y".
+    /// Groups and brackets are nested at most 32 deep and the alg expands to at most 10000 moves and pauses.
+    pub fn from_string(s: &str) -> Result<Self, Error> {
+        let mut parser = Parser::new(s);
+        let alg = sequence(&mut parser)?;
+        if parser.peek().is_some() {
+            return Err(parser.error(parser.pos));
+        }
+        Ok(alg)
+    }
+
+    /// The inverse alg, keeping the structure, eg. `[A, B]` -> `[B, A]`, `[A: B]` -> `[A: B']`.
+    pub fn invert(&self) -> Self {
+        match self {
+            Alg::Move(m) => Alg::Move(m.get_inverse()),
+            Alg::Sequence(algs) => Alg::Sequence(algs.iter().rev().map(|a| a.invert()).collect()),
+            Alg::Group(a, n) => Alg::Group(Box::new(a.invert()), *n),
+            Alg::Commutator(a, b) => Alg::Commutator(b.clone(), a.clone()),
+            Alg::Conjugate(a, b) => Alg::Conjugate(a.clone(), Box::new(b.invert())),
+            Alg::Pause => Alg::Pause,
+        }
+    }
+
+    /// The mirror across the `plane`, keeping the structure, eg. `R U R'` -> `L' U' L` across the M plane.
+    pub fn mirror(&self, plane: Plane) -> Self {
+        match self {
+            Alg::Move(m) => Alg::Move(m.mirror(plane)),
+            Alg::Sequence(algs) => Alg::Sequence(algs.iter().map(|a| a.mirror(plane)).collect()),
+            Alg::Group(a, n) => Alg::Group(Box::new(a.mirror(plane)), *n),
+            Alg::Commutator(a, b) => Alg::Commutator(Box::new(a.mirror(plane)), Box::new(b.mirror(plane))),
+            Alg::Conjugate(a, b) => Alg::Conjugate(Box::new(a.mirror(plane)), Box::new(b.mirror(plane))),
+            Alg::Pause => Alg::Pause,
+        }
+    }
+
+    /// The number of moves and pauses of the expanded alg.
+    fn expanded_len(&self) -> usize {
+        match self {
+            Alg::Move(_) | Alg::Pause => 1,
+            Alg::Sequence(s) => s.iter().map(|a| a.expanded_len()).sum(),
+            Alg::Group(a, n) => a.expanded_len() * n,
+            Alg::Commutator(a, b) => 2 * (a.expanded_len() + b.expanded_len()),
+            Alg::Conjugate(a, b) => 2 * a.expanded_len() + b.expanded_len(),
+        }
+    }
+
+    /// Expand the groups, commutators and conjugates, i.e, a flat sequence of moves and pauses.
+    /// An alg parsed by `from_string` expands to at most 10000 moves and pauses.
+    pub fn expand(&self) -> Self {
+        let mut algs = Vec::new();
+        self.expand_into(&mut algs);
+        Alg::Sequence(algs)
+    }
+
+    fn expand_into(&self, algs: &mut Vec<Alg>) {
+        match self {
+            Alg::Move(_) | Alg::Pause => algs.push(self.clone()),
+            Alg::Sequence(s) => s.iter().for_each(|a| a.expand_into(algs)),
+            Alg::Group(a, n) => {
+                for _ in 0..*n {
+                    a.expand_into(algs);
+                }
+            }
+            Alg::Commutator(a, b) => {
+                a.expand_into(algs);
+                b.expand_into(algs);
+                a.invert().expand_into(algs);
+                b.invert().expand_into(algs);
+            }
+            Alg::Conjugate(a, b) => {
+                a.expand_into(algs);
+                b.expand_into(algs);
+                a.invert().expand_into(algs);
+            }
+        }
+    }
+
+    /// The moves of the alg, pauses are dropped.
+    pub fn to_formula(&self) -> Formula {
+        let moves = match self.expand() {
+            Alg::Sequence(algs) => algs
+                .into_iter()
+                .filter_map(|a| match a {
+                    Alg::Move(m) => Some(m),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        Formula { moves }
+    }
+}
+
+impl FromStr for Alg {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Alg::from_string(s)
+    }
+}
+
+impl From<&Formula> for Alg {
+    fn from(formula: &Formula) -> Self {
+        Alg::Sequence(formula.moves.iter().map(|m| Alg::Move(*m)).collect())
+    }
+}
+
+impl From<&Alg> for Formula {
+    fn from(alg: &Alg) -> Self {
+        alg.to_formula()
+    }
+}
+
+impl fmt::Display for Alg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alg::Move(m) => write!(f, "{}", m),
+            Alg::Sequence(algs) => {
+                let s: Vec<String> = algs.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}", s.join(" "))
+            }
+            Alg::Group(a, 1) => write!(f, "({})", a),
+            Alg::Group(a, n) => write!(f, "({}){}", a, n),
+            Alg::Commutator(a, b) => write!(f, "[{}, {}]", a, b),
+            Alg::Conjugate(a, b) => write!(f, "[{}: {}]", a, b),
+            Alg::Pause => write!(f, "."),
+        }
+    }
+}
+
+/// Parse algs until the end, a closing bracket or a separator; a single alg isn't wrapped in a sequence.
+fn sequence(parser: &mut Parser) -> Result<Alg, Error> {
+    let mut algs = Vec::new();
+    let mut len = 0;
+    loop {
+        parser.skip();
+        let start = parser.pos;
+        let count = algs.len();
+        match parser.peek() {
+            None | Some(')' | ']' | ',' | ':') => break,
+            Some('.') => {
+                parser.pos += 1;
+                algs.push(Alg::Pause);
+            }
+            Some('(') => {
+                parser.open(start)?;
+                let alg = sequence(parser)?;
+                parser.close(start, ')')?;
+                let n = parser.repeat()?;
+                algs.push(Alg::Group(Box::new(alg), n));
+            }
+            Some('[') => {
+                parser.open(start)?;
+                let a = Box::new(sequence(parser)?);
+                let sep = parser.peek();
+                if !matches!(sep, Some(',' | ':')) {
+                    return Err(parser.error(start));
+                }
+                parser.pos += 1;
+                let b = Box::new(sequence(parser)?);
+                parser.close(start, ']')?;
+                match sep {
+                    Some(',') => algs.push(Alg::Commutator(a, b)),
+                    _ => algs.push(Alg::Conjugate(a, b)),
+                }
+            }
+            Some(_) => algs.extend(parser.single()?.map(Alg::Move)),
+        }
+        len += algs[count..]
+            .iter()
+            .map(|a| a.expanded_len())
+            .sum::<usize>();
+        if len > MAX_MOVES {
+            return Err(parser.error(start));
+        }
+    }
+    match algs.len() {
+        1 => Ok(algs.remove(0)),
+        _ => Ok(Alg::Sequence(algs)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubie::CubieCube;
+    use crate::moves::Move::*;
+
+    #[test]
+    fn test_parse_print() {
+        for s in [
+            "R U R' U'",
+            "[R U R', D]",
+            "[F: [R, U]]",
+            "(R U R' U')3 . y",
+            "[Rw: (U R U' R')2] x2",
+            "()",
+        ] {
+            assert_eq!(Alg::from_string(s).unwrap().to_string(), s);
+        }
+        let alg = Alg::from_string("[r u r' , D2] // comment").unwrap();
+        assert_eq!(alg.to_string(), "[Rw Uw Rw', D2]");
+        assert!(Alg::from_string("[R U, D").is_err());
+        assert!(Alg::from_string("[R U D]").is_err());
+        assert!(Alg::from_string("(R U").is_err());
+        assert!(Alg::from_string("(R U)101").is_err());
+        assert!(Alg::from_string("R U]").is_err());
+        let position = |s: &str| match Alg::from_string(s) {
+            Err(Error::InvalidScramble { position, .. }) => position,
+            r => panic!("Unexpected result: {:?}", r),
+        };
+        assert_eq!(position("((((R)100)100)100)100"), 1);
+        assert_eq!(position(&"(".repeat(200_000)), 32);
+        assert_eq!(position(&"[".repeat(200_000)), 32);
+        // [[R, U], U] expands to 2 * (4 + 1) moves, 12 nested commutators expand to 12286.
+        let nested = |n: usize| format!("{}R, U]{}", "[".repeat(n), ", U]".repeat(n - 1));
+        assert_eq!(position(&nested(12)), 0);
+        let alg = Alg::from_string(&nested(11)).unwrap();
+        assert_eq!(alg.to_formula().moves.len(), 6142);
+        let alg = Alg::from_string("((R)100)100").unwrap();
+        assert_eq!(alg.to_formula().moves.len(), 10_000);
+    }
+
+    #[test]
+    fn test_expand() {
+        let alg = Alg::from_string("[R U R', D]").unwrap();
+        assert_eq!(alg.to_formula().moves, vec![R, U, R3, D, R, U3, R3, D3]);
+        let alg = Alg::from_string("[F: [R, U]] .").unwrap();
+        assert_eq!(alg.to_formula().moves, vec![F, R, U, R3, U3, F3]);
+        assert_eq!(alg.expand().to_string(), "F R U R' U' F' .");
+        let alg = Alg::from_string("(R U R' U')6").unwrap();
+        let cc = CubieCube::default().apply_formula(&alg.to_formula());
+        assert_eq!(cc, CubieCube::default());
+    }
+
+    #[test]
+    fn test_invert_mirror() {
+        for s in ["[R U R', D]", "[F: [R, U]] x", "(Rw U' M2)2 . [y: S E]"] {
+            let alg = Alg::from_string(s).unwrap();
+            let cc = CubieCube::default()
+                .apply_formula(&alg.to_formula())
+                .apply_formula(&alg.invert().to_formula());
+            assert_eq!(cc, CubieCube::default());
+            for plane in [Plane::M, Plane::E, Plane::S] {
+                assert_eq!(alg.mirror(plane).mirror(plane), alg);
+            }
+        }
+        let alg = Alg::from_string("[F: R U R' U']").unwrap();
+        assert_eq!(alg.invert().to_string(), "[F: U R U' R']");
+        assert_eq!(alg.mirror(Plane::M).to_string(), "[F': L' U' L U]");
+        let alg = Alg::from_string("Rw M x y").unwrap();
+        assert_eq!(alg.mirror(Plane::M).to_string(), "Lw' M x y'");
+        assert_eq!(alg.mirror(Plane::E).to_string(), "Rw' M' x' y");
+    }
+}
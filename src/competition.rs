@@ -0,0 +1,210 @@
+//! # Competition
+//! Scramble sets of the 3x3x3 events of a competition round:
+//! * `Event::Standard` (333): random state scrambles.
+//! * `Event::Blindfolded` (333bf): random state scrambles followed by a random orientation in wide moves.
+//! * `Event::FewestMoves` (333fm): random state scrambles wrapped in `R' U' F ... R' U' F`.
+//!
+//! A round has named groups, each with its scrambles and extra scrambles(eg. 5 + 2),
+//! the set is exported as JSON with the `serde` feature.
+//! # Example
+//! ```rust
+//! use rcuber::competition::{Event, ScrambleSet};
+//! use rcuber::moves::ScrambleOptions;
+//!
+//! fn main() {
+//!     let options = ScrambleOptions { seed: Some(42), ..Default::default() };
+//!     let set = ScrambleSet::new(Event::Standard, &["A", "B"], 5, 2, &options).unwrap();
+//!     assert_eq!(set.groups[1].name, "B");
+//!     assert_eq!(set.groups[1].scrambles.len(), 5);
+//!     assert_eq!(set.groups[1].extras.len(), 2);
+//! }
+//! ```
+
+use rand::Rng;
+
+use crate::error::Error;
+use crate::generator::Generator;
+use crate::moves::{Formula, ScrambleOptions};
+
+/// The 3x3x3 events with random state scrambles.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    #[cfg_attr(feature = "serde", serde(rename = "333"))]
+    Standard,
+    #[cfg_attr(feature = "serde", serde(rename = "333bf"))]
+    Blindfolded,
+    #[cfg_attr(feature = "serde", serde(rename = "333fm"))]
+    FewestMoves,
+}
+
+impl Event {
+    /// The WCA id of the event, eg. "333bf".
+    pub fn id(self) -> &'static str {
+        match self {
+            Event::Standard => "333",
+            Event::Blindfolded => "333bf",
+            Event::FewestMoves => "333fm",
+        }
+    }
+
+    /// Generate a scramble of the event with the length bounds and seed of the `options`.
+    pub fn scramble(self, options: &ScrambleOptions) -> Result<Formula, Error> {
+        match self {
+            Event::Standard => Formula::random_state_scramble_with(options),
+            Event::Blindfolded => Formula::bld_scramble_with(options),
+            Event::FewestMoves => Formula::fmc_scramble_with(options),
+        }
+    }
+}
+
+/// A named group of a round, eg. "A", with its scrambles and extra scrambles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrambleGroup {
+    pub name: String,
+    pub scrambles: Vec<Formula>,
+    pub extras: Vec<Formula>,
+}
+
+/// The scrambles of a round of an event, by groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrambleSet {
+    pub event: Event,
+    pub groups: Vec<ScrambleGroup>,
+}
+
+impl ScrambleSet {
+    /// Generate `scrambles` scrambles and `extras` extra scrambles for each of the `groups`.
+    /// With a seed in the `options` the whole set is reproducible, each scramble is seeded from it.
+    pub fn new(
+        event: Event,
+        groups: &[&str],
+        scrambles: usize,
+        extras: usize,
+        options: &ScrambleOptions,
+    ) -> Result<Self, Error> {
+        let mut rng = options.seed.map(Generator::seeded_rng);
+        let mut scramble = || {
+            let options = ScrambleOptions {
+                seed: rng.as_mut().map(|rng| rng.gen()),
+                ..options.clone()
+            };
+            event.scramble(&options)
+        };
+        let mut set = Self {
+            event,
+            groups: Vec::new(),
+        };
+        for name in groups {
+            set.groups.push(ScrambleGroup {
+                name: name.to_string(),
+                scrambles: (0..scrambles)
+                    .map(|_| scramble())
+                    .collect::<Result<_, _>>()?,
+                extras: (0..extras).map(|_| scramble()).collect::<Result<_, _>>()?,
+            });
+        }
+        Ok(set)
+    }
+
+    /// The set as JSON, eg. `{"event":"333fm","groups":[{"name":"A","scrambles":["R' U' F ..."],"extras":[...]}]}`.
+    /// # Example
+    /// ```rust
+    /// use rcuber::competition::{Event, ScrambleSet};
+    /// use rcuber::moves::ScrambleOptions;
+    ///
+    /// fn main() {
+    ///     let options = ScrambleOptions { seed: Some(42), ..Default::default() };
+    ///     let set = ScrambleSet::new(Event::Standard, &["A"], 5, 2, &options).unwrap();
+    ///     assert!(set.to_json().starts_with("{\"event\":\"333\",\"groups\":[{\"name\":\"A\""));
+    /// }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        // The events, names and formulas are all serialized as strings, this can't fail.
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubie::CubieCube;
+    use crate::moves::Move::*;
+
+    #[test]
+    fn test_event_scrambles() {
+        let options = ScrambleOptions {
+            seed: Some(7),
+            ..Default::default()
+        };
+        let scramble = Event::Standard.scramble(&options).unwrap();
+        assert_eq!(
+            scramble,
+            Formula::random_state_scramble_with(&options).unwrap()
+        );
+
+        let fmc = Event::FewestMoves.scramble(&options).unwrap();
+        let wrap = [R3, U3, F];
+        let n = fmc.moves.len();
+        assert_eq!(fmc.moves[..3], wrap);
+        assert_eq!(fmc.moves[n - 3..], wrap);
+        assert_ne!(fmc.moves[3].axis(), F.axis());
+        assert_ne!(fmc.moves[n - 4].axis(), R.axis());
+        // The random state of the seed.
+        let cc = Generator::random_with_rng(&mut Generator::seeded_rng(7));
+        assert_eq!(CubieCube::default().apply_formula(&fmc), cc);
+
+        let bld = Event::Blindfolded.scramble(&options).unwrap();
+        let face_turns = bld.moves.iter().take_while(|m| !m.is_wide()).count();
+        let orientation = &bld.moves[face_turns..];
+        assert!(orientation.len() <= 2);
+        let wide = [Rw, Rw2, Rw3, Fw, Fw3, Uw, Uw2, Uw3];
+        assert!(orientation.iter().all(|m| wide.contains(m)));
+        assert_eq!(
+            Formula {
+                moves: bld.moves[..face_turns].to_vec()
+            },
+            scramble
+        );
+    }
+
+    #[test]
+    fn test_scramble_set() {
+        let options = ScrambleOptions {
+            seed: Some(2024),
+            ..Default::default()
+        };
+        let set = ScrambleSet::new(Event::FewestMoves, &["A", "B\"1"], 1, 2, &options).unwrap();
+        assert_eq!(
+            set,
+            ScrambleSet::new(Event::FewestMoves, &["A", "B\"1"], 1, 2, &options).unwrap()
+        );
+        assert_ne!(set.groups[0].extras[0], set.groups[0].extras[1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json() {
+        let options = ScrambleOptions {
+            seed: Some(2024),
+            ..Default::default()
+        };
+        let set = ScrambleSet::new(Event::FewestMoves, &["A", "B\"1"], 1, 2, &options).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&set.to_json()).unwrap();
+        assert_eq!(json["event"], "333fm");
+        assert_eq!(json["groups"].as_array().unwrap().len(), 2);
+        assert_eq!(json["groups"][1]["name"], "B\"1");
+        assert_eq!(json["groups"][1]["scrambles"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            json["groups"][1]["extras"][1],
+            set.groups[1].extras[1].to_string()
+        );
+        assert_eq!(
+            serde_json::from_str::<ScrambleSet>(&set.to_json()).unwrap(),
+            set
+        );
+    }
+}
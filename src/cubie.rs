@@ -1,4 +1,4 @@
-use rand::random;
+use rand::Rng;
 use static_init::dynamic;
 use std::fmt;
 use std::ops::Mul;
@@ -13,7 +13,8 @@ use crate::{facelet::*, moves::*};
 /// Example: `ULB` (Up, Left, Bottom).
 #[rustfmt::skip]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Corner {
     URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB,
 }
@@ -65,6 +66,7 @@ impl TryFrom<&str> for Corner {
 #[rustfmt::skip]
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Edge {
     UR, UF, UL, UB, DR, DF, DL, DB, FR, FL, BL, BR,
 }
@@ -121,6 +123,8 @@ impl TryFrom<&str> for Edge {
 
 /// Cube on the cubie level.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CubieCubeFields"))]
 pub struct CubieCube {
     /// Center permutation, relative to SOLVED_STATE.
     pub center: [Color; 6],
@@ -134,6 +138,36 @@ pub struct CubieCube {
     pub eo: [u8; 12],
 }
 
+/// The fields of a deserialized `CubieCube`, before they are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CubieCubeFields {
+    center: [Color; 6],
+    cp: [Corner; 8],
+    co: [u8; 8],
+    ep: [Edge; 12],
+    eo: [u8; 12],
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CubieCubeFields> for CubieCube {
+    type Error = Error;
+    fn try_from(fields: CubieCubeFields) -> Result<Self, Self::Error> {
+        if fields.co.iter().any(|o| *o > 2) || fields.eo.iter().any(|o| *o > 1) {
+            return Err(Error::InvalidCubieValue);
+        }
+        let cc = CubieCube {
+            center: fields.center,
+            cp: fields.cp,
+            co: fields.co,
+            ep: fields.ep,
+            eo: fields.eo,
+        };
+        cc.verify()?;
+        Ok(cc)
+    }
+}
+
 /// Solved cube on the Cubie level.
 pub const SOLVED_CUBIE_CUBE: CubieCube = CubieCube {
     center: [Color::U, Color::R, Color::F, Color::D, Color::L, Color::B],
@@ -190,6 +224,75 @@ impl fmt::Display for CubieCube {
     }
 }
 
+/// A cycle of pieces in the order they move, eg. URF -> UBR -> ULB, with the orientation change after the whole cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T> {
+    pub pieces: Vec<T>,
+    /// Twist of the corners(1 clockwise, 2 counter-clockwise), flip of the edges(1), always 0 for the centers.
+    pub orientation: u8,
+}
+
+impl<T: fmt::Display> fmt::Display for Cycle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pieces: Vec<String> = self.pieces.iter().map(|p| p.to_string()).collect();
+        let orientation = match self.orientation {
+            0 => "",
+            1 => "+",
+            _ => "-",
+        };
+        write!(f, "({}){}", pieces.join(" "), orientation)
+    }
+}
+
+/// The cycle decomposition of a cube, the pieces at their solved positions are left out.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cycles {
+    pub corners: Vec<Cycle<Corner>>,
+    pub edges: Vec<Cycle<Edge>>,
+    pub centers: Vec<Cycle<Color>>,
+}
+
+impl fmt::Display for Cycles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut cycles: Vec<String> = self.corners.iter().map(|c| c.to_string()).collect();
+        cycles.extend(self.edges.iter().map(|c| c.to_string()));
+        cycles.extend(self.centers.iter().map(|c| c.to_string()));
+        write!(f, "{}", cycles.join(" "))
+    }
+}
+
+/// Decompose a permutation(`perm[i]` is the piece at position i) with the orientations into cycles of positions.
+fn cycles_of(perm: &[usize], ori: &[u8], n_ori: u8) -> Vec<(Vec<usize>, u8)> {
+    let mut cycles = Vec::new();
+    let mut visited = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        if visited[start] {
+            continue;
+        }
+        let mut cycle = Vec::new();
+        let mut o = 0;
+        let mut i = start;
+        while !visited[i] {
+            visited[i] = true;
+            cycle.push(i);
+            o = (o + ori[i]) % n_ori;
+            // The piece at position i moves to the position holding the piece from i.
+            i = perm.iter().position(|p| *p == i).unwrap();
+        }
+        if cycle.len() > 1 || o != 0 {
+            cycles.push((cycle, o));
+        }
+    }
+    cycles
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
 impl From<&Vec<Move>> for CubieCube {
     fn from(moves: &Vec<Move>) -> Self {
         CubieCube::default().apply_moves(moves)
@@ -419,6 +522,13 @@ impl CubieCube {
             .for_each(|&m| self.multiply(AMCT.amc[m as usize]));
     }
 
+    /// Get the twist of the 8 corners. 0 <= twist < 2187 in phase 1, twist = 0 in phase 2.
+    pub fn get_twist(&self) -> u16 {
+        self.co[(URF as usize)..(DRB as usize)]
+            .iter()
+            .fold(0, |twist, co| 3 * twist + *co as u16)
+    }
+
     /// Set the twist of the 8 corners. 0 <= twist < 2187 in phase 1, twist = 0 in phase 2.
     pub fn set_twist(&mut self, twist: u16) {
         let mut twistparity = 0;
@@ -431,6 +541,13 @@ impl CubieCube {
         self.co[DRB as usize] = (3 - twistparity % 3) % 3;
     }
 
+    /// Get the flip of the 12 edges. 0 <= flip < 2048 in phase 1, flip = 0 in phase 2.
+    pub fn get_flip(&self) -> u16 {
+        self.eo[(UR as usize)..(BR as usize)]
+            .iter()
+            .fold(0, |flip, eo| 2 * flip + *eo as u16)
+    }
+
     /// Set the flip of the 12 edges. 0 <= flip < 2048 in phase 1, flip = 0 in phase 2.
     pub fn set_flip(&mut self, flip: u16) {
         let mut flipparity = 0;
@@ -486,6 +603,89 @@ impl CubieCube {
         }
     }
 
+    /// Get the permutation and location of the UD-slice edges FR,FL,BL and BR.
+    ///
+    /// 0 <= slice_sorted < 11880 in phase 1, 0 <= slice_sorted < 24 in phase 2, slice_sorted = 0 for solved cube.
+    pub fn get_slice_sorted(&self) -> u16 {
+        let mut a = 0;
+        let mut _x = 0;
+        let mut edge4 = [FR; 4];
+        // First compute the index a < (12 choose 4) and the slice edges array edge4.
+        for j in ((UR as usize)..=(BR as usize)).rev() {
+            if FR <= self.ep[j] && self.ep[j] <= BR {
+                a += c_nk((11 - j) as u32, _x + 1);
+                edge4[3 - _x as usize] = self.ep[j];
+                _x += 1;
+            }
+        }
+        // Then compute the index b < 4! for the permutation in edge4.
+        let mut b = 0;
+        for j in (1..4).rev() {
+            let mut k = 0;
+            while edge4[j] as usize != j + 8 {
+                rotate_left(&mut edge4, 0, j);
+                k += 1;
+            }
+            b = (j + 1) * b + k;
+        }
+        (24 * a as usize + b) as u16
+    }
+
+    /// Set the permutation and location of the UD-slice edges FR,FL,BL and BR, the other edges are placed in order.
+    ///
+    /// 0 <= slice_sorted < 11880 in phase 1, 0 <= slice_sorted < 24 in phase 2, slice_sorted = 0 for solved cube.
+    pub fn set_slice_sorted(&mut self, idx: u16) {
+        let mut slice_edge = [FR, FL, BL, BR];
+        let other_edge = [UR, UF, UL, UB, DR, DF, DL, DB];
+        let mut b = idx % 24; // Permutation
+        let mut a = idx / 24; // Location
+        let mut ep = [-1; 12];
+
+        for j in 1..4 {
+            let mut k = b % (j as u16 + 1);
+            b /= j as u16 + 1;
+            while k > 0 {
+                rotate_right(&mut slice_edge, 0, j);
+                k -= 1;
+            }
+        }
+        let mut _x: i32 = 4; // set slice edges
+        for j in ALL_EDGES {
+            if a >= c_nk(11 - j as u32, _x as u32) as u16 {
+                self.ep[j as usize] = slice_edge[(4 - _x) as usize];
+                ep[j as usize] = slice_edge[(4 - _x) as usize] as i32;
+                a -= c_nk(11 - j as u32, _x as u32) as u16;
+                _x -= 1;
+            }
+        }
+        let mut _x = 0; // set the remaining edges UR..DB
+        for j in ALL_EDGES {
+            if ep[j as usize] == -1 {
+                self.ep[j as usize] = other_edge[_x];
+                _x += 1;
+            }
+        }
+    }
+
+    /// Get the permutation of the 8 corners.
+    ///
+    /// 0 <= corners < 40320 defined but unused in phase 1, 0 <= corners < 40320 in phase 2,
+    ///
+    /// corners = 0 for solved cube
+    pub fn get_corners(&self) -> u16 {
+        let mut perm = self.cp;
+        let mut b = 0;
+        for j in ((URF as usize + 1)..=(DRB as usize)).rev() {
+            let mut k = 0;
+            while perm[j] as usize != j {
+                rotate_left(&mut perm, 0, j);
+                k += 1;
+            }
+            b = (j + 1) * b + k;
+        }
+        b as u16
+    }
+
     /// Set the permutation of the 8 corners.
     ///
     /// 0 <= corners < 40320 defined but unused in phase 1, 0 <= corners < 40320 in phase 2,
@@ -504,29 +704,92 @@ impl CubieCube {
         }
     }
 
-    /// Generate a random cube. The probability is the same for all possible states.
-    pub fn randomize(&mut self) {
-        // The permutation of the 12 edges. 0 <= idx < 12!."""
-        let mut idx = random::<usize>() % 479001600; // 12!
-        self.cp = ALL_CORNERS.clone();
+    /// Get the permutation of the 12 edges.
+    ///
+    /// 0 <= edges < 479001600 (12!), edges = 0 for solved cube
+    pub fn get_edges(&self) -> u32 {
+        let mut perm = self.ep;
+        let mut b = 0;
+        for j in ((UR as usize + 1)..=(BR as usize)).rev() {
+            let mut k = 0;
+            while perm[j] as usize != j {
+                rotate_left(&mut perm, 0, j);
+                k += 1;
+            }
+            b = (j as u32 + 1) * b + k;
+        }
+        b
+    }
+
+    /// Set the permutation of the 12 edges.
+    ///
+    /// 0 <= edges < 479001600 (12!), edges = 0 for solved cube
+    pub fn set_edges(&mut self, idx: u32) {
+        self.ep = ALL_EDGES;
+        let mut idx = idx;
         for j in ALL_EDGES {
-            let mut k = idx % (j as usize + 1);
-            idx /= j as usize + 1;
+            let mut k = idx % (j as u32 + 1);
+            idx /= j as u32 + 1;
             while k > 0 {
                 rotate_right(&mut self.ep, 0, j as usize);
                 k -= 1;
             }
         }
-        let p = self.edge_parity();
-        loop {
-            self.set_corners(random::<u16>() % 40320); // 8!
-            if p == self.corner_parity() {
-                // parities of edge and corner permutations must be the same
-                break;
-            }
+    }
+
+    /// Index of the cube among all the 43,252,003,274,489,856,000 states, 0 for solved cube.
+    /// The cube must be valid(see `verify`), the centers are not indexed.
+    /// # Example
+    /// ```rust
+    /// use rcuber::cubie::CubieCube;
+    /// use rcuber::moves::Formula;
+    ///
+    /// fn main() {
+    ///     let cc = CubieCube::default().apply_formula(&Formula::scramble());
+    ///     let idx = cc.index();
+    ///     assert_eq!(CubieCube::from_index(idx).unwrap(), cc);
+    /// }
+    /// ```
+    pub fn index(&self) -> u128 {
+        // The parity of the corner permutation is determined by the edge permutation,
+        // so only half of the edge permutations are counted for each corner permutation.
+        let mut idx = self.get_corners() as u128;
+        idx = idx * 2187 + self.get_twist() as u128;
+        idx = idx * 239500800 + (self.get_edges() / 2) as u128;
+        idx * 2048 + self.get_flip() as u128
+    }
+
+    /// The cube of an `index`, the inverse of `index`. The centers are solved.
+    pub fn from_index(idx: u128) -> Result<Self, Error> {
+        if idx >= N_STATES {
+            return Err(Error::InvalidCubieValue);
+        }
+        let mut cc = CubieCube::default();
+        cc.set_flip((idx % 2048) as u16);
+        let idx = idx / 2048;
+        let edges = (idx % 239500800) as u32;
+        let idx = idx / 239500800;
+        cc.set_twist((idx % 2187) as u16);
+        cc.set_corners((idx / 2187) as u16);
+        cc.set_edges(edges * 2);
+        if cc.edge_parity() != cc.corner_parity() {
+            cc.set_edges(edges * 2 + 1);
         }
-        self.set_flip(random::<u16>() % 2048); // 2^11
-        self.set_twist(random::<u16>() % 2187); // 3^7
+        Ok(cc)
+    }
+
+    /// Generate a random cube. The probability is the same for all possible states.
+    pub fn randomize(&mut self) {
+        self.randomize_with_rng(&mut rand::thread_rng());
+    }
+
+    /// Generate a random cube with the `rng`, eg. a seeded rng for reproducible states. The centers are kept.
+    pub fn randomize_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let cc = CubieCube::from_index(rng.gen_range(0..N_STATES)).unwrap();
+        self.cp = cc.cp;
+        self.co = cc.co;
+        self.ep = cc.ep;
+        self.eo = cc.eo;
     }
 
     /// Returns the number of corner twist needed to orient the corners.
@@ -601,6 +864,9 @@ impl CubieCube {
             let c = ci as usize;
             d.cp[self.cp[c] as usize] = ci;
         }
+        for (i, c) in self.center.iter().enumerate() {
+            d.center[*c as usize] = ALL_COLORS[i];
+        }
         for ci in ALL_CORNERS {
             let c = ci as usize;
             let ori = self.co[d.cp[c] as usize];
@@ -616,6 +882,80 @@ impl CubieCube {
         d
     }
 
+    /// The cycles of the corners and edges with their orientation changes, and the cycles of the centers.
+    /// # Example
+    /// ```rust
+    /// use rcuber::cubie::CubieCube;
+    /// use rcuber::moves::Formula;
+    ///
+    /// fn main() {
+    ///     let f = Formula::from_string("R U R' D R U' R' D'").unwrap();
+    ///     let cc = CubieCube::default().apply_formula(&f);
+    ///     assert_eq!(cc.cycles().to_string(), "(URF DFR DLF)");
+    ///     assert_eq!(cc.order(), 3);
+    /// }
+    /// ```
+    pub fn cycles(&self) -> Cycles {
+        let cp: Vec<usize> = self.cp.iter().map(|c| *c as usize).collect();
+        let ep: Vec<usize> = self.ep.iter().map(|e| *e as usize).collect();
+        let center: Vec<usize> = self.center.iter().map(|c| *c as usize).collect();
+        Cycles {
+            corners: cycles_of(&cp, &self.co, 3)
+                .into_iter()
+                .map(|(c, o)| Cycle {
+                    pieces: c.iter().map(|i| ALL_CORNERS[*i]).collect(),
+                    orientation: o,
+                })
+                .collect(),
+            edges: cycles_of(&ep, &self.eo, 2)
+                .into_iter()
+                .map(|(c, o)| Cycle {
+                    pieces: c.iter().map(|i| ALL_EDGES[*i]).collect(),
+                    orientation: o,
+                })
+                .collect(),
+            centers: cycles_of(&center, &[0; 6], 1)
+                .into_iter()
+                .map(|(c, _)| Cycle {
+                    pieces: c.iter().map(|i| ALL_COLORS[*i]).collect(),
+                    orientation: 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// The order of the cube, i.e, the number of repetitions of its moves to return to solved.
+    pub fn order(&self) -> usize {
+        let cycles = self.cycles();
+        let lengths = cycles
+            .corners
+            .iter()
+            .map(|c| c.pieces.len() * if c.orientation == 0 { 1 } else { 3 })
+            .chain(cycles.edges.iter().map(|c| c.pieces.len() * if c.orientation == 0 { 1 } else { 2 }))
+            .chain(cycles.centers.iter().map(|c| c.pieces.len()));
+        lengths.fold(1, |order, l| order / gcd(order, l) * l)
+    }
+
+    /// The cube to the power `n`, i.e, its moves repeated `n` times.
+    pub fn pow(&self, n: u32) -> Self {
+        let mut result = CubieCube::default();
+        let mut base = *self;
+        let mut n = n;
+        while n > 0 {
+            if n % 2 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            n /= 2;
+        }
+        result
+    }
+
+    /// The commutator `a b a' b'`.
+    pub fn commutator(a: &CubieCube, b: &CubieCube) -> Self {
+        *a * *b * a.inverse_cubie_cube() * b.inverse_cubie_cube()
+    }
+
     /// Give the parity of the corner permutation.
     pub fn corner_parity(&self) -> bool {
         let mut s = 0;
@@ -642,7 +982,57 @@ impl CubieCube {
         (s % 2) == 0
     }
 
-    /// Check if cubiecube is valid.
+    /// Give the parity of the center permutation. Slice moves and rotations permute the centers.
+    pub fn center_parity(&self) -> bool {
+        let mut s = 0;
+        for i in (1..6).rev() {
+            for j in (0..i).rev() {
+                if self.center[j] > self.center[i] {
+                    s += 1;
+                }
+            }
+        }
+        (s % 2) == 0
+    }
+
+    /// Mirror the cube across the `plane`, so that `cube.apply_formula(f).mirror(plane)` equals
+    /// `cube.mirror(plane).apply_formula(&f.mirror(plane))`.
+    pub fn mirror(&self, plane: Plane) -> Self {
+        // The positions swapped by the mirror, as indices of the corners, edges and centers.
+        let (corners, edges, centers): ([usize; 8], [usize; 12], [usize; 6]) = match plane {
+            Plane::M => (
+                [1, 0, 3, 2, 5, 4, 7, 6],
+                [2, 1, 0, 3, 6, 5, 4, 7, 9, 8, 11, 10],
+                [0, 4, 2, 3, 1, 5],
+            ),
+            Plane::E => (
+                [4, 5, 6, 7, 0, 1, 2, 3],
+                [4, 5, 6, 7, 0, 1, 2, 3, 8, 9, 10, 11],
+                [3, 1, 2, 0, 4, 5],
+            ),
+            Plane::S => (
+                [3, 2, 1, 0, 7, 6, 5, 4],
+                [0, 3, 2, 1, 4, 7, 6, 5, 11, 10, 9, 8],
+                [0, 1, 5, 3, 4, 2],
+            ),
+        };
+        let mut cc = *self;
+        for i in 0..8 {
+            cc.cp[corners[i]] = Corner::try_from(corners[self.cp[i] as usize] as u8).unwrap();
+            // A mirror reverses the clockwise order of the facelets of the corners.
+            cc.co[corners[i]] = (3 - self.co[i]) % 3;
+        }
+        for i in 0..12 {
+            cc.ep[edges[i]] = Edge::try_from(edges[self.ep[i] as usize] as u8).unwrap();
+            cc.eo[edges[i]] = self.eo[i];
+        }
+        for i in 0..6 {
+            cc.center[centers[i]] = SOLVED_CUBIE_CUBE.center[centers[self.center[i] as usize]];
+        }
+        cc
+    }
+
+    /// Check if cubiecube is valid, see `validate` for the details of an invalid cube.
     pub fn verify(&self) -> Result<bool, Error> {
         let mut edge_count = [0; 12];
         for i in ALL_EDGES {
@@ -658,7 +1048,7 @@ impl CubieCube {
             s += self.eo[i as usize];
         }
         if s % 2 != 0 {
-            return Err(Error::InvalidEdge);
+            return Err(Error::FlipError);
         }
 
         let mut corner_count = [0; 8];
@@ -675,11 +1065,11 @@ impl CubieCube {
             s += self.co[i as usize];
         }
         if s % 3 != 0 {
-            return Err(Error::InvalidCorner);
+            return Err(Error::TwistError);
         }
 
         if self.edge_parity() != self.corner_parity() {
-            return Err(Error::InvalidCubieValue);
+            return Err(Error::ParityError);
         }
         Ok(true)
     }
@@ -776,9 +1166,121 @@ pub fn c_nk(n: u32, k: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use crate::cubie::*;
+    use crate::moves::{Formula, Plane};
     #[cfg(feature = "term")]
     use crate::printer::print_facelet;
 
+    #[test]
+    fn test_mirror() {
+        for _ in 0..20 {
+            let state = CubieCube::default().apply_formula(&Formula::scramble());
+            let f = Formula::from_string("R U R' Uw2 M' x S E' Bw y' Lw z2 Dw' F").unwrap();
+            for plane in [Plane::M, Plane::E, Plane::S] {
+                let mirrored = state.mirror(plane);
+                assert_eq!(mirrored.mirror(plane), state);
+                assert!(mirrored.verify().is_ok());
+                assert_eq!(
+                    mirrored.apply_formula(&f.mirror(plane)),
+                    state.apply_formula(&f).mirror(plane)
+                );
+            }
+        }
+        let state = CubieCube::default().apply_formula(&Formula::from_string("R U R'").unwrap());
+        assert_eq!(
+            state.mirror(Plane::M),
+            CubieCube::default().apply_formula(&Formula::from_string("L' U' L").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_coordinates() {
+        for _ in 0..50 {
+            let cc = CubieCube::default().apply_formula(&Formula::scramble());
+            let mut c = CubieCube::default();
+            c.set_twist(cc.get_twist());
+            assert_eq!(c.co, cc.co);
+            c.set_flip(cc.get_flip());
+            assert_eq!(c.eo, cc.eo);
+            c.set_corners(cc.get_corners());
+            assert_eq!(c.cp, cc.cp);
+            c.set_edges(cc.get_edges());
+            assert_eq!(c.ep, cc.ep);
+            c.set_slice_sorted(cc.get_slice_sorted());
+            assert_eq!(c.get_slice_sorted(), cc.get_slice_sorted());
+            assert_eq!(c.get_slice(), cc.get_slice());
+        }
+        for i in [0, 1, 23, 24, 11879] {
+            let mut c = CubieCube::default();
+            c.set_slice_sorted(i);
+            assert_eq!(c.get_slice_sorted(), i);
+        }
+        let mut c = CubieCube::default();
+        c.set_edges(479001599);
+        assert_eq!(c.get_edges(), 479001599);
+        assert_eq!(CubieCube::default().get_slice_sorted(), 0);
+    }
+
+    #[test]
+    fn test_index() {
+        assert_eq!(CubieCube::default().index(), 0);
+        assert_eq!(CubieCube::from_index(0).unwrap(), CubieCube::default());
+        assert!(CubieCube::from_index(N_STATES).is_err());
+        let last = CubieCube::from_index(N_STATES - 1).unwrap();
+        assert!(last.verify().is_ok());
+        assert_eq!(last.index(), N_STATES - 1);
+        for _ in 0..100 {
+            let cc = CubieCube::default().apply_formula(&Formula::scramble());
+            assert_eq!(CubieCube::from_index(cc.index()).unwrap(), cc);
+            let idx = rand::random::<u128>() % N_STATES;
+            let cc = CubieCube::from_index(idx).unwrap();
+            assert!(cc.verify().is_ok());
+            assert_eq!(cc.index(), idx);
+        }
+    }
+
+    #[test]
+    fn test_cycles() {
+        let cube = |s: &str| CubieCube::default().apply_formula(&Formula::from_string(s).unwrap());
+        let t_perm = cube("R U R' U' R' F R2 U' R' U' R U R' F'");
+        assert_eq!(t_perm.cycles().to_string(), "(URF UBR) (UR UL)");
+        assert_eq!(t_perm.order(), 2);
+        let sune = cube("R U R' U R U2 R'");
+        assert_eq!(sune.order(), 6);
+        assert_eq!(cube("R U").order(), 105);
+        assert_eq!(cube("M2 U M2 U2 M2 U M2").cycles().to_string(), "(UR UL) (UF UB)");
+        let mut twisted = CubieCube::default();
+        twisted.co[0] = 1;
+        twisted.co[1] = 2;
+        assert_eq!(twisted.cycles().to_string(), "(URF)+ (UFL)-");
+        assert_eq!(twisted.order(), 3);
+        let superflip = cube("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2");
+        assert_eq!(superflip.cycles().edges.len(), 12);
+        assert!(superflip.cycles().edges.iter().all(|c| c.pieces.len() == 1 && c.orientation == 1));
+        assert_eq!(superflip.order(), 2);
+        assert_eq!(cube("M").cycles().centers.len(), 1);
+        assert_eq!(cube("M").order(), 4);
+        assert_eq!(CubieCube::default().cycles().to_string(), "");
+        assert_eq!(CubieCube::default().order(), 1);
+        for c in [t_perm, sune, cube("R U Rw' x S2 y")] {
+            assert_eq!(c.pow(c.order() as u32), CubieCube::default());
+            assert_eq!(c.pow(3), c * c * c);
+        }
+    }
+
+    #[test]
+    fn test_commutator() {
+        let a = CubieCube::default().apply_formula(&Formula::from_string("R U R'").unwrap());
+        let b = CubieCube::default().apply_move(D);
+        let c = CubieCube::commutator(&a, &b);
+        let f = Formula::from_string("R U R' D R U' R' D'").unwrap();
+        assert_eq!(c, CubieCube::default().apply_formula(&f));
+        assert_eq!(c.cycles().corners.len(), 1);
+        assert_eq!(c.cycles().corners[0].pieces.len(), 3);
+        assert!(c.cycles().edges.is_empty());
+        let m = CubieCube::default().apply_moves(&[M, x]);
+        assert_eq!(m * m.inverse_cubie_cube(), CubieCube::default());
+    }
+
     #[test]
     fn test_eq() {
         let state = CubieCube::default();
@@ -814,6 +1316,9 @@ mod tests {
 
         assert_eq!(state.corner_parity(), true);
         assert_eq!(state.edge_parity(), true);
+        assert!(state.center_parity());
+        assert!(!state.apply_move(M).center_parity());
+        assert!(state.apply_move(M2).center_parity());
 
         let state = CubieCube::from(&vec![R, U, R3, U3, R3, F, R, F3]);
 
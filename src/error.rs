@@ -7,12 +7,17 @@ use thiserror::Error;
 ///   * Error 4: Not all corners exist exactly once
 ///   * Error 5: Twist error: One corner has to be twisted
 ///   * Error 6: Parity error: Two corners or two edges have to be exchanged
-///   * Error 7: Invalid scramble string
+///   * Error 7: Invalid scramble string, with the position(in characters) of the bad token
 ///   * Error 8: Invalid facelet string
 ///   * Error 9: Invalid cubie reperesentation
 ///   * Error 10: Invalid cubie reperesentation
 ///   * Error 11: No solution exists for the given maxDepth
 ///   * Error 12: Probe limit exceeded, no solution within given probMax
+///   * Error 13: A solving stage failed, eg. its precondition is not met or no solution found
+///   * Error 14: Invalid Reid(Singmaster) string
+///   * Error 15: Unknown case of an algorithm set, eg. OLL 58
+///   * Error 16: Invalid state spec, with the bad token
+///   * Error 17: Unsupported metric
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Invalid color value")]
@@ -27,8 +32,8 @@ pub enum Error {
     TwistError,
     #[error("Two corners or two edges have to be exchanged")]
     ParityError,
-    #[error("Invalid scramble string")]
-    InvalidScramble,
+    #[error("Invalid scramble string, unexpected {token:?} at position {position}")]
+    InvalidScramble { position: usize, token: String },
     #[error("Invalid facelet string")]
     InvalidFaceletString,
     #[error("Invalid facelet reperesentation")]
@@ -39,6 +44,18 @@ pub enum Error {
     NoSolutionForMaxDepth,
     #[error("Probe limit exceeded")]
     ProbeLimitExceeded,
+    #[error("{stage} failed: {reason}")]
+    StageFailed { stage: &'static str, reason: String },
+    #[error("Invalid Reid string")]
+    InvalidReidString,
+    #[error("Unknown {set} case {case:?}")]
+    InvalidCase { set: &'static str, case: String },
+    #[error("Invalid state spec, unexpected {token:?}")]
+    InvalidStateSpec { token: String },
+    #[error("Unsupported metric")]
+    UnsupportedMetric,
+    #[error("No state is {distance} moves from solved, at most {max}")]
+    InvalidDistance { distance: usize, max: usize },
 }
 
 
@@ -6,6 +6,7 @@ use crate::moves::Move;
 /// Names the colors of the cube facelets: up, right, front, down, left, back.
 #[rustfmt::skip]
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     U, R, F, D, L, B,
 }
@@ -153,6 +154,22 @@ impl fmt::Display for FaceCube {
     }
 }
 
+/// Serialize as the 54 characters facelet string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FaceCube {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FaceCube {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        FaceCube::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
 /// The names of the facelet positions of the cube.
 ///
 /// ```text
@@ -314,4 +331,30 @@ mod tests {
         println!("{:?}", &cc);
 
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let s = "FFFFUFFFFUUUURUUUURRRRFRRRRBBBBDBBBBDDDDLDDDDLLLLBLLLL";
+        let fc = FaceCube::try_from(s).unwrap();
+        let json = serde_json::to_string(&fc).unwrap();
+        assert_eq!(json, format!("\"{}\"", s));
+        assert_eq!(serde_json::from_str::<FaceCube>(&json).unwrap(), fc);
+        assert!(serde_json::from_str::<FaceCube>("\"UUU\"").is_err());
+        assert_eq!(serde_json::to_string(&Color::R).unwrap(), "\"R\"");
+
+        let cc = CubieCube::try_from(&fc).unwrap();
+        let json = serde_json::to_string(&cc).unwrap();
+        assert_eq!(serde_json::from_str::<CubieCube>(&json).unwrap(), cc);
+        let mut invalid = cc;
+        invalid.co[0] = 255;
+        let json = serde_json::to_string(&invalid).unwrap();
+        assert!(serde_json::from_str::<CubieCube>(&json).is_err());
+        let mut invalid = cc;
+        invalid.ep.swap(0, 1);
+        let json = serde_json::to_string(&invalid).unwrap();
+        assert!(serde_json::from_str::<CubieCube>(&json).is_err());
+        assert_eq!(serde_json::to_string(&DLF).unwrap(), "\"DLF\"");
+        assert_eq!(serde_json::from_str::<crate::cubie::Edge>("\"BR\"").unwrap(), BR);
+    }
 }
@@ -0,0 +1,363 @@
+//! # Formats
+//! Interchange formats of the cube state with other tools:
+//! * Reid (Singmaster) notation: the stickers of the 12 edge and 8 corner positions,
+//!   solved is `UF UR UB UL DF DR DB DL FR FL BR BL UFR URB UBL ULF DRF DFL DLB DBR`.
+//! * Facelet strings in the face order of kociemba/min2phase (URFDLB) and cubing.js (ULFRBD),
+//!   the 9 facelets of each face are in the same order as `FaceCube`.
+//! * cubing.js `KPattern` data, the pieces and orientations of the edges, corners and centers (in Reid order).
+//! # Example
+//! ```rust
+//! use rcuber::cubie::CubieCube;
+//! use rcuber::facelet::FaceCube;
+//! use rcuber::formats::FaceOrder;
+//! use rcuber::moves::Move;
+//!
+//! fn main() {
+//!     let cc = CubieCube::default().apply_move(Move::R);
+//!     let reid = cc.to_reid().unwrap();
+//!     assert_eq!(reid, "UF FR UB UL DF BR DB DL DR FL UR BL FDR FRU UBL ULF BRD DFL DLB BUR");
+//!     assert_eq!(CubieCube::from_reid(&reid).unwrap(), cc);
+//!     let fc = FaceCube::try_from(&cc).unwrap();
+//!     let s = fc.to_string_in(FaceOrder::CubingJs);
+//!     assert_eq!(FaceCube::from_str_in(&s, FaceOrder::CubingJs).unwrap(), fc);
+//! }
+//! ```
+
+use crate::cubie::{Corner, CubieCube, Edge};
+use crate::error::Error;
+use crate::facelet::{Color, FaceCube, CORNER_FACELET, EDGE_FACELET};
+
+/// Edge positions in Reid order.
+const REID_EDGES: [&str; 12] = [
+    "UF", "UR", "UB", "UL", "DF", "DR", "DB", "DL", "FR", "FL", "BR", "BL",
+];
+
+/// Corner positions in Reid order.
+const REID_CORNERS: [&str; 8] = ["UFR", "URB", "UBL", "ULF", "DRF", "DFL", "DLB", "DBR"];
+
+/// The edges in Reid order.
+const REID_EDGE_ORDER: [Edge; 12] = [
+    Edge::UF,
+    Edge::UR,
+    Edge::UB,
+    Edge::UL,
+    Edge::DF,
+    Edge::DR,
+    Edge::DB,
+    Edge::DL,
+    Edge::FR,
+    Edge::FL,
+    Edge::BR,
+    Edge::BL,
+];
+
+/// The corners in Reid order.
+const REID_CORNER_ORDER: [Corner; 8] = [
+    Corner::URF,
+    Corner::UBR,
+    Corner::ULB,
+    Corner::UFL,
+    Corner::DFR,
+    Corner::DLF,
+    Corner::DBL,
+    Corner::DRB,
+];
+
+/// Face orders of the facelet strings.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum FaceOrder {
+    /// URFDLB, the order of kociemba and min2phase (and `FaceCube`).
+    Kociemba,
+    /// ULFRBD, the order of cubing.js (KPuzzle) and twizzle.
+    CubingJs,
+}
+
+impl FaceOrder {
+    /// The faces in the order.
+    pub fn faces(self) -> [Color; 6] {
+        use Color::*;
+        match self {
+            FaceOrder::Kociemba => [U, R, F, D, L, B],
+            FaceOrder::CubingJs => [U, L, F, R, B, D],
+        }
+    }
+}
+
+/// The facelets of a position named by its faces in any order, eg. "UFR", following the order of the name.
+fn position_facelets(name: &str) -> Option<Vec<usize>> {
+    let faces: Vec<usize> = name
+        .chars()
+        .map(|c| Color::try_from(c).map(|c| c as usize))
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let pieces: Vec<Vec<usize>> = match faces.len() {
+        2 => EDGE_FACELET
+            .iter()
+            .map(|e| e.iter().map(|f| *f as usize).collect())
+            .collect(),
+        3 => CORNER_FACELET
+            .iter()
+            .map(|c| c.iter().map(|f| *f as usize).collect())
+            .collect(),
+        _ => return None,
+    };
+    pieces.into_iter().find_map(|facelets| {
+        faces
+            .iter()
+            .map(|face| facelets.iter().find(|f| **f / 9 == *face).copied())
+            .collect()
+    })
+}
+
+impl FaceCube {
+    /// Parse a cube in Reid notation, the centers are at the solved positions.
+    pub fn from_reid(s: &str) -> Result<Self, Error> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        if tokens.len() != 20 {
+            return Err(Error::InvalidReidString);
+        }
+        let mut face = FaceCube::default();
+        for (name, token) in REID_EDGES.iter().chain(REID_CORNERS.iter()).zip(tokens) {
+            let facelets = position_facelets(name).unwrap();
+            if token.chars().count() != facelets.len() {
+                return Err(Error::InvalidReidString);
+            }
+            for (f, c) in facelets.into_iter().zip(token.chars()) {
+                face.f[f] = Color::try_from(c).map_err(|_| Error::InvalidReidString)?;
+            }
+        }
+        Ok(face)
+    }
+
+    /// The cube in Reid notation, eg. `UF UR UB UL DF DR DB DL FR FL BR BL UFR URB UBL ULF DRF DFL DLB DBR`.
+    pub fn to_reid(&self) -> String {
+        let tokens: Vec<String> = REID_EDGES
+            .iter()
+            .chain(REID_CORNERS.iter())
+            .map(|name| {
+                position_facelets(name)
+                    .unwrap()
+                    .into_iter()
+                    .map(|f| self.f[f].to_string())
+                    .collect()
+            })
+            .collect();
+        tokens.join(" ")
+    }
+
+    /// Parse a facelet string of the face `order`.
+    pub fn from_str_in(s: &str, order: FaceOrder) -> Result<Self, Error> {
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() != 54 {
+            return Err(Error::InvalidFaceletString);
+        }
+        let mut face = FaceCube::default();
+        for (i, c) in order.faces().iter().enumerate() {
+            for j in 0..9 {
+                face.f[*c as usize * 9 + j] = Color::try_from(chars[i * 9 + j])?;
+            }
+        }
+        Ok(face)
+    }
+
+    /// The facelet string of the face `order`.
+    pub fn to_string_in(&self, order: FaceOrder) -> String {
+        order
+            .faces()
+            .iter()
+            .flat_map(|c| self.f[*c as usize * 9..*c as usize * 9 + 9].iter())
+            .map(|c| c.to_string())
+            .collect()
+    }
+}
+
+impl CubieCube {
+    /// Parse a cube in Reid notation, eg. `UF UR UB UL DF DR DB DL FR FL BR BL UFR URB UBL ULF DRF DFL DLB DBR`.
+    pub fn from_reid(s: &str) -> Result<Self, Error> {
+        CubieCube::try_from(&FaceCube::from_reid(s)?)
+    }
+
+    /// The cube in Reid notation, rotated to the solved centers.
+    pub fn to_reid(&self) -> Result<String, Error> {
+        Ok(FaceCube::try_from(self)?.to_reid())
+    }
+}
+
+/// The pieces and orientations of an orbit of cubing.js `KPattern`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KPatternOrbit {
+    pub pieces: Vec<u8>,
+    pub orientation: Vec<u8>,
+}
+
+/// The data of a cubing.js `KPattern` of the 3x3x3 puzzle, the pieces are in Reid order
+/// and the centers in ULFRBD order. The orientations of the centers aren't tracked, they are 0.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+pub struct KPatternData {
+    pub edges: KPatternOrbit,
+    pub corners: KPatternOrbit,
+    pub centers: KPatternOrbit,
+}
+
+impl From<&CubieCube> for KPatternData {
+    fn from(cc: &CubieCube) -> Self {
+        let faces = FaceOrder::CubingJs.faces();
+        let edges = KPatternOrbit {
+            pieces: REID_EDGE_ORDER
+                .iter()
+                .map(|e| {
+                    REID_EDGE_ORDER
+                        .iter()
+                        .position(|p| *p == cc.ep[*e as usize])
+                        .unwrap() as u8
+                })
+                .collect(),
+            orientation: REID_EDGE_ORDER.iter().map(|e| cc.eo[*e as usize]).collect(),
+        };
+        let corners = KPatternOrbit {
+            pieces: REID_CORNER_ORDER
+                .iter()
+                .map(|c| {
+                    REID_CORNER_ORDER
+                        .iter()
+                        .position(|p| *p == cc.cp[*c as usize])
+                        .unwrap() as u8
+                })
+                .collect(),
+            orientation: REID_CORNER_ORDER
+                .iter()
+                .map(|c| cc.co[*c as usize])
+                .collect(),
+        };
+        let centers = KPatternOrbit {
+            pieces: faces
+                .iter()
+                .map(|c| {
+                    faces
+                        .iter()
+                        .position(|p| *p == cc.center[*c as usize])
+                        .unwrap() as u8
+                })
+                .collect(),
+            orientation: vec![0; 6],
+        };
+        Self {
+            edges,
+            corners,
+            centers,
+        }
+    }
+}
+
+impl TryFrom<&KPatternData> for CubieCube {
+    type Error = Error;
+    fn try_from(data: &KPatternData) -> Result<Self, Self::Error> {
+        let orbits = [
+            (&data.edges, 12, 2),
+            (&data.corners, 8, 3),
+            (&data.centers, 6, 4),
+        ];
+        for (orbit, n, n_ori) in orbits {
+            let mut pieces = orbit.pieces.clone();
+            pieces.sort();
+            if pieces != (0..n).collect::<Vec<u8>>()
+                || orbit.orientation.len() != n as usize
+                || orbit.orientation.iter().any(|o| *o >= n_ori)
+            {
+                return Err(Error::InvalidCubieValue);
+            }
+        }
+        let mut cc = CubieCube::default();
+        for (i, e) in REID_EDGE_ORDER.iter().enumerate() {
+            cc.ep[*e as usize] = REID_EDGE_ORDER[data.edges.pieces[i] as usize];
+            cc.eo[*e as usize] = data.edges.orientation[i];
+        }
+        for (i, c) in REID_CORNER_ORDER.iter().enumerate() {
+            cc.cp[*c as usize] = REID_CORNER_ORDER[data.corners.pieces[i] as usize];
+            cc.co[*c as usize] = data.corners.orientation[i];
+        }
+        let faces = FaceOrder::CubingJs.faces();
+        for (i, c) in faces.iter().enumerate() {
+            cc.center[*c as usize] = faces[data.centers.pieces[i] as usize];
+        }
+        Ok(cc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moves::Formula;
+
+    #[test]
+    fn test_reid() {
+        let solved = "UF UR UB UL DF DR DB DL FR FL BR BL UFR URB UBL ULF DRF DFL DLB DBR";
+        assert_eq!(FaceCube::default().to_reid(), solved);
+        assert_eq!(CubieCube::from_reid(solved).unwrap(), CubieCube::default());
+        for _ in 0..10 {
+            let cc = CubieCube::default().apply_formula(&Formula::scramble());
+            let reid = cc.to_reid().unwrap();
+            assert_eq!(CubieCube::from_reid(&reid).unwrap(), cc);
+        }
+        assert!(FaceCube::from_reid("UF UR").is_err());
+        assert!(FaceCube::from_reid(&solved.replace("UFR", "UF")).is_err());
+        assert!(FaceCube::from_reid(&solved.replace("UFR", "UFX")).is_err());
+        assert!(CubieCube::from_reid(&solved.replace("UF UR", "UR UF")).is_err());
+    }
+
+    #[test]
+    fn test_face_order() {
+        let fc = FaceCube::default();
+        assert_eq!(
+            fc.to_string_in(FaceOrder::CubingJs),
+            "UUUUUUUUULLLLLLLLLFFFFFFFFFRRRRRRRRRBBBBBBBBBDDDDDDDDD"
+        );
+        assert_eq!(fc.to_string_in(FaceOrder::Kociemba), fc.to_string());
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        let fc = FaceCube::try_from(&cc).unwrap();
+        for order in [FaceOrder::Kociemba, FaceOrder::CubingJs] {
+            let s = fc.to_string_in(order);
+            assert_eq!(FaceCube::from_str_in(&s, order).unwrap(), fc);
+            assert_eq!(
+                &s[27..36],
+                &fc.to_string()[order.faces()[3] as usize * 9..][..9]
+            );
+        }
+        assert!(FaceCube::from_str_in("UUU", FaceOrder::CubingJs).is_err());
+    }
+
+    #[test]
+    fn test_kpattern() {
+        let data = KPatternData::from(&CubieCube::default());
+        assert_eq!(data.edges.pieces, (0..12).collect::<Vec<u8>>());
+        assert_eq!(data.centers.pieces, (0..6).collect::<Vec<u8>>());
+        let r = KPatternData::from(
+            &CubieCube::default().apply_formula(&Formula::from_string("R").unwrap()),
+        );
+        assert_eq!(r.corners.pieces, [4, 0, 2, 3, 7, 5, 6, 1]);
+        assert_eq!(r.corners.orientation, [2, 1, 0, 0, 1, 0, 0, 2]);
+        assert_eq!(r.edges.pieces, [0, 8, 2, 3, 4, 10, 6, 7, 5, 9, 1, 11]);
+        for s in ["R U F' D2 L B'", "M E S y"] {
+            let cc = CubieCube::default().apply_formula(&Formula::from_string(s).unwrap());
+            let data = KPatternData::from(&cc);
+            assert_eq!(CubieCube::try_from(&data).unwrap(), cc);
+        }
+        let mut data = KPatternData::from(&CubieCube::default());
+        data.corners.orientation[0] = 3;
+        assert!(CubieCube::try_from(&data).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_kpattern_serde() {
+        let data = KPatternData::from(&CubieCube::default());
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(
+            json.starts_with(r#"{"EDGES":{"pieces":[0,1,2,3,4,5,6,7,8,9,10,11],"orientation":"#)
+        );
+        assert_eq!(serde_json::from_str::<KPatternData>(&json).unwrap(), data);
+    }
+}
@@ -1,8 +1,61 @@
-use rand::random;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use static_init::dynamic;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 use crate::{
+    constants::ALL_MOVES,
     cubie::{Corner, CubieCube, Edge},
-    facelet::Color,
+    error::Error,
+    facelet::{Color, FaceCube},
+    moves::{Formula, Metric, Move},
+    solver::{
+        cfop::{OLLSolver, PLLSolver},
+        min2phase::{constants::OPTIMAL_SOLUTION, solver::Solver},
+        roux::CMLLSolver,
+    },
+    spec::StateSpec,
+};
+
+/// The quarter turns.
+const QUARTER_TURNS: [Move; 12] = [
+    Move::U,
+    Move::U3,
+    Move::R,
+    Move::R3,
+    Move::F,
+    Move::F3,
+    Move::D,
+    Move::D3,
+    Move::L,
+    Move::L3,
+    Move::B,
+    Move::B3,
+];
+
+/// Depth of `QTM_BALL`.
+const QTM_BALL_DEPTH: u8 = 6;
+
+/// The QTM distances of the states at most `QTM_BALL_DEPTH` quarter turns from solved, by the state index.
+#[dynamic(lazy)]
+static QTM_BALL: HashMap<u128, u8> = {
+    let mut ball = HashMap::from([(CubieCube::default().index(), 0)]);
+    let mut layer = vec![CubieCube::default()];
+    for depth in 1..=QTM_BALL_DEPTH {
+        let mut next = Vec::new();
+        for cc in &layer {
+            for m in QUARTER_TURNS {
+                let cc = cc.apply_move(m);
+                if let Entry::Vacant(e) = ball.entry(cc.index()) {
+                    e.insert(depth);
+                    next.push(cc);
+                }
+            }
+        }
+        layer = next;
+    }
+    ball
 };
 
 /// CubieCube generator.
@@ -12,7 +65,7 @@ impl Generator {
     const RANDOM: i8 = -1;
     const SOLVED: i8 = -2;
 
-    fn fill_perm(arr: &[i8]) -> Vec<u8> {
+    fn fill_perm<R: Rng + ?Sized>(arr: &[i8], rng: &mut R) -> Vec<u8> {
         let cnt = arr.len();
         let mut res = vec![0; cnt];
         for i in 0..cnt {
@@ -26,7 +79,7 @@ impl Generator {
         while Generator::verify_perm(&res) != true {
             for i in 0..cnt {
                 if arr[i] == Generator::RANDOM {
-                    res[i] = (random::<usize>() % cnt) as u8;
+                    res[i] = rng.gen_range(0..cnt as u32) as u8;
                 }
             }
         }
@@ -45,7 +98,7 @@ impl Generator {
         true
     }
 
-    fn fill_ori(arr: &[i8]) -> Vec<u8> {
+    fn fill_ori<R: Rng + ?Sized>(arr: &[i8], rng: &mut R) -> Vec<u8> {
         let cnt = arr.len();
         let mut res = vec![0; cnt];
         let ori = match cnt {
@@ -55,7 +108,7 @@ impl Generator {
         loop {
             for i in 0..cnt {
                 match arr[i] {
-                    Generator::RANDOM => res[i] = random::<u8>() % ori,
+                    Generator::RANDOM => res[i] = rng.gen_range(0..ori),
                     _ => res[i] = arr[i] as u8,
                 };
             }
@@ -66,39 +119,39 @@ impl Generator {
         res
     }
 
-    fn fix_parity(cc: &mut CubieCube, cp: &[i8; 8], ep: &[i8; 12]) {
+    fn fix_parity<R: Rng + ?Sized>(cc: &mut CubieCube, cp: &[i8; 8], ep: &[i8; 12], rng: &mut R) {
         if cp == &[Generator::SOLVED; 8] {
-            Generator::swap_edge(&mut cc.ep, ep);
+            Generator::swap_edge(&mut cc.ep, ep, rng);
             return;
         }
         if ep == &[Generator::SOLVED; 12] {
-            Generator::swap_corner(&mut cc.cp, cp);
+            Generator::swap_corner(&mut cc.cp, cp, rng);
             return;
         }
-        match random::<u8>() % 2 == 0 {
-            true => Generator::swap_edge(&mut cc.ep, ep),
-            false => Generator::swap_corner(&mut cc.cp, cp),
+        match rng.gen_range(0..2u32) == 0 {
+            true => Generator::swap_edge(&mut cc.ep, ep, rng),
+            false => Generator::swap_corner(&mut cc.cp, cp, rng),
         }
     }
 
-    fn swap_corner(cp: &mut [Corner; 8], cp_o: &[i8; 8]) {
+    fn swap_corner<R: Rng + ?Sized>(cp: &mut [Corner; 8], cp_o: &[i8; 8], rng: &mut R) {
         let idxx = Generator::get_unfill(cp_o);
         let m = idxx.len();
-        let p1 = random::<usize>() % m;
-        let mut p2 = random::<usize>() % m;
+        let p1 = rng.gen_range(0..m as u32) as usize;
+        let mut p2 = rng.gen_range(0..m as u32) as usize;
         while p1 == p2 {
-            p2 = random::<usize>() % m;
+            p2 = rng.gen_range(0..m as u32) as usize;
         }
         cp.swap(idxx[p1], idxx[p2]);
     }
 
-    fn swap_edge(ep: &mut [Edge; 12], ep_o: &[i8; 12]) {
+    fn swap_edge<R: Rng + ?Sized>(ep: &mut [Edge; 12], ep_o: &[i8; 12], rng: &mut R) {
         let idxx = Generator::get_unfill(ep_o);
         let m = idxx.len();
-        let p1 = random::<usize>() % m;
-        let mut p2 = random::<usize>() % m;
+        let p1 = rng.gen_range(0..m as u32) as usize;
+        let mut p2 = rng.gen_range(0..m as u32) as usize;
         while p1 == p2 {
-            p2 = random::<usize>() % m;
+            p2 = rng.gen_range(0..m as u32) as usize;
         }
         ep.swap(idxx[p1], idxx[p2]);
     }
@@ -113,28 +166,201 @@ impl Generator {
         r
     }
 
-    fn gen_edge(ep: [i8; 12], eo: [i8; 12]) -> ([Edge; 12], [u8; 12]) {
+    /// Shuffle the pieces at the `positions` uniformly, Fisher-Yates with portable draws.
+    pub(crate) fn shuffle<T, R: Rng + ?Sized>(arr: &mut [T], positions: &[usize], rng: &mut R) {
+        for i in (1..positions.len()).rev() {
+            let j = rng.gen_range(0..=i as u32) as usize;
+            arr.swap(positions[i], positions[j]);
+        }
+    }
+
+    /// Orient the pieces at the `positions` uniformly, the total orientation stays 0 modulo `ori`.
+    pub(crate) fn twist<R: Rng + ?Sized>(
+        arr: &mut [u8],
+        positions: &[usize],
+        ori: u8,
+        rng: &mut R,
+    ) {
+        let mut sum = 0;
+        for (i, p) in positions.iter().enumerate() {
+            arr[*p] = match i + 1 == positions.len() {
+                true => (ori - sum % ori) % ori,
+                false => rng.gen_range(0..ori),
+            };
+            sum += arr[*p];
+        }
+    }
+
+    /// The corner permutations of the <R, U> group, 120 of the 720 permutations of the 6 corners.
+    fn two_gen_corners() -> Vec<[Corner; 8]> {
+        let mut perms = vec![CubieCube::default()];
+        let mut i = 0;
+        while i < perms.len() {
+            for m in [Move::R, Move::U] {
+                let cc = perms[i].apply_move(m);
+                if perms.iter().all(|p| p.cp != cc.cp) {
+                    perms.push(cc);
+                }
+            }
+            i += 1;
+        }
+        perms.into_iter().map(|cc| cc.cp).collect()
+    }
+
+    /// A random U turn(or none).
+    fn auf<R: Rng + ?Sized>(rng: &mut R) -> Move {
+        [Move::N, Move::U, Move::U2, Move::U3][rng.gen_range(0..4u32) as usize]
+    }
+
+    /// The state solved by the `algorithm`.
+    fn unsolve(cube: CubieCube, algorithm: Vec<Move>) -> CubieCube {
+        cube.apply_formula(&Formula { moves: algorithm }.inverse())
+    }
+
+    /// The positions(0-3 in the U layer, 4 in the slot) and orientations of the corner and the edge of the 41 F2L cases of the front right slot.
+    fn f2l_cases() -> Vec<[(usize, u8); 2]> {
+        let top = |n: u8| (0..4).flat_map(move |p| (0..n).map(move |o| (p, o)));
+        let slot = |n: u8| (0..n).map(|o| (4, o));
+        let pairs = top(3)
+            .flat_map(|c| top(2).map(move |e| [c, e]))
+            .chain(slot(3).flat_map(|c| top(2).map(move |e| [c, e])))
+            .chain(top(3).flat_map(|c| slot(2).map(move |e| [c, e])))
+            .chain(slot(3).flat_map(|c| slot(2).map(move |e| [c, e])))
+            .filter(|pair| *pair != [(4, 0), (4, 0)]);
+        // Cases are the same up to an AUF, i.e, a shift of the U layer positions.
+        let mut seen = HashSet::new();
+        pairs
+            .filter(|pair| {
+                let key = (0..4)
+                    .map(|k| pair.map(|(p, o)| (if p < 4 { (p + k) % 4 } else { p }, o)))
+                    .min()
+                    .unwrap();
+                seen.insert(key)
+            })
+            .collect()
+    }
+
+    /// One state of each ZBLL case of the OCLL `oll`, the cases are the same up to a U turn before and after.
+    fn zbll_cases(oll: &str) -> Result<Vec<CubieCube>, Error> {
+        let number = match oll {
+            "H" => 21,
+            "Pi" => 22,
+            "U" => 23,
+            "T" => 24,
+            "L" => 25,
+            "Antisune" => 26,
+            "Sune" => 27,
+            _ => {
+                return Err(Error::InvalidCase {
+                    set: "ZBLL",
+                    case: oll.to_string(),
+                })
+            }
+        };
+        let algorithm = OLLSolver::new(CubieCube::default())
+            .algorithm(number)
+            .unwrap();
+        let co = Generator::unsolve(CubieCube::default(), algorithm).co;
+        let perms: Vec<[usize; 4]> = (0..256)
+            .map(|i| [i / 64, i / 16 % 4, i / 4 % 4, i % 4])
+            .filter(|p| (0..4).all(|j| !p[..j].contains(&p[j])))
+            .collect();
+        let aufs: Vec<CubieCube> = [Move::N, Move::U, Move::U2, Move::U3]
+            .iter()
+            .map(|m| CubieCube::default().apply_move(*m))
+            .collect();
+        let key = |cc: &CubieCube| -> Vec<u8> {
+            let cp = cc.cp.iter().map(|c| *c as u8);
+            let ep = cc.ep.iter().map(|e| *e as u8);
+            cp.chain(cc.co).chain(ep).collect()
+        };
+        let mut seen = HashSet::new();
+        let mut cases = Vec::new();
+        for cp in &perms {
+            for ep in &perms {
+                let mut cc = CubieCube {
+                    co,
+                    ..CubieCube::default()
+                };
+                for i in 0..4 {
+                    cc.cp[i] = Corner::try_from(cp[i] as u8).unwrap();
+                    cc.ep[i] = Edge::try_from(ep[i] as u8).unwrap();
+                }
+                if cc.corner_parity() != cc.edge_parity() {
+                    continue;
+                }
+                let canonical = aufs
+                    .iter()
+                    .flat_map(|a| aufs.iter().map(move |b| key(&(*a * cc * *b))))
+                    .min()
+                    .unwrap();
+                if seen.insert(canonical) {
+                    cases.push(cc);
+                }
+            }
+        }
+        Ok(cases)
+    }
+
+    /// Check if the `cube` is solvable in at most `budget` quarter turns, meeting the `QTM_BALL` in the middle.
+    fn qtm_within(cube: &CubieCube, budget: usize) -> bool {
+        if let Some(d) = QTM_BALL.get(&cube.index()) {
+            return *d as usize <= budget;
+        }
+        budget > QTM_BALL_DEPTH as usize
+            && QUARTER_TURNS
+                .iter()
+                .any(|m| Generator::qtm_within(&cube.apply_move(*m), budget - 1))
+    }
+
+    /// Check if the optimal solution of the `cube`, reached by `n` moves, has `n` moves in the `metric`.
+    fn is_at_distance(cube: &CubieCube, n: usize, metric: Metric) -> bool {
+        if n == 0 {
+            return *cube == CubieCube::default();
+        }
+        match metric {
+            // A quarter turn changes the corner parity, a shorter solution has at most `n - 2` moves.
+            Metric::QTM => n < 2 || !Generator::qtm_within(cube, n - 2),
+            _ => {
+                let facelet = FaceCube::try_from(cube).unwrap().to_string();
+                // The optimal search may miss a solution of exactly max_depth moves, one more move is allowed.
+                Solver::default()
+                    .solve(&facelet, n + 1, u64::MAX, 0, OPTIMAL_SOLUTION)
+                    .is_ok_and(|solution| solution.moves.len() == n)
+            }
+        }
+    }
+
+    fn gen_edge<R: Rng + ?Sized>(
+        ep: [i8; 12],
+        eo: [i8; 12],
+        rng: &mut R,
+    ) -> ([Edge; 12], [u8; 12]) {
         // let ep = [-1; 12];
-        let epv = Generator::fill_perm(&ep);
+        let epv = Generator::fill_perm(&ep, rng);
         let mut ep = [Edge::UR; 12];
         for i in 0..12 {
             ep[i] = Edge::try_from(epv[i]).unwrap();
         }
         // let eo = [-1; 12];
-        let eo = Generator::fill_ori(&eo);
+        let eo = Generator::fill_ori(&eo, rng);
         let eo: [u8; 12] = eo.try_into().unwrap();
         (ep, eo)
     }
 
-    fn gen_corner(cp: [i8; 8], co: [i8; 8]) -> ([Corner; 8], [u8; 8]) {
+    fn gen_corner<R: Rng + ?Sized>(
+        cp: [i8; 8],
+        co: [i8; 8],
+        rng: &mut R,
+    ) -> ([Corner; 8], [u8; 8]) {
         // let cp = [-1; 8];
-        let cpv = Generator::fill_perm(&cp);
+        let cpv = Generator::fill_perm(&cp, rng);
         let mut cp = [Corner::URF; 8];
         for i in 0..8 {
             cp[i] = Corner::try_from(cpv[i]).unwrap();
         }
         // let co = [-1; 8];
-        let co = Generator::fill_ori(&co);
+        let co = Generator::fill_ori(&co, rng);
         let co: [u8; 8] = co.try_into().unwrap();
         (cp, co)
     }
@@ -146,8 +372,19 @@ impl Generator {
     /// `co, eo`: -1 = Generator::RANDOM, other valid number (0-2 for co, 0-1 for eo) for expected Co/Eo.
     ///
     pub fn gen_state(cpv: [i8; 8], cov: [i8; 8], epv: [i8; 12], eov: [i8; 12]) -> CubieCube {
-        let (cp, co) = Generator::gen_corner(cpv, cov);
-        let (ep, eo) = Generator::gen_edge(epv, eov);
+        Generator::gen_state_with_rng(cpv, cov, epv, eov, &mut rand::thread_rng())
+    }
+
+    /// Generate a CubieCube based on defined cp, co, ep, eo with the `rng`, see `gen_state`.
+    pub fn gen_state_with_rng<R: Rng + ?Sized>(
+        cpv: [i8; 8],
+        cov: [i8; 8],
+        epv: [i8; 12],
+        eov: [i8; 12],
+        rng: &mut R,
+    ) -> CubieCube {
+        let (cp, co) = Generator::gen_corner(cpv, cov, rng);
+        let (ep, eo) = Generator::gen_edge(epv, eov, rng);
         let center = [Color::U, Color::R, Color::F, Color::D, Color::L, Color::B];
 
         let mut cc = CubieCube {
@@ -158,11 +395,22 @@ impl Generator {
             eo,
         };
         if cc.verify().is_err() {
-            Generator::fix_parity(&mut cc, &cpv, &epv);
+            Generator::fix_parity(&mut cc, &cpv, &epv, rng);
         }
         cc
     }
 
+    /// Generate a state matching the textual `spec`, uniformly, see `StateSpec`.
+    /// eg. "solved: DF DR DB DL DFR; oriented: U-layer edges; random: rest".
+    pub fn gen_spec(spec: &str) -> Result<CubieCube, Error> {
+        Generator::gen_spec_with_rng(spec, &mut rand::thread_rng())
+    }
+
+    /// `gen_spec` with the `rng`.
+    pub fn gen_spec_with_rng<R: Rng + ?Sized>(spec: &str, rng: &mut R) -> Result<CubieCube, Error> {
+        Ok(spec.parse::<StateSpec>()?.sample(rng))
+    }
+
     /// Generate a random CubieCube using cc.randomize().
     pub fn random() -> CubieCube {
         let mut cc = CubieCube::default();
@@ -170,94 +418,449 @@ impl Generator {
         cc
     }
 
+    /// Generate a random CubieCube with the `rng`, see `CubieCube::randomize_with_rng`.
+    pub fn random_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
+        let mut cc = CubieCube::default();
+        cc.randomize_with_rng(rng);
+        cc
+    }
+
+    /// A portable rng seeded by `seed`, the same seed gives the same states and scrambles on every platform.
+    pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(seed)
+    }
+
     /// Generate a random CubieCube use general gen_state method.
     pub fn random2() -> CubieCube {
+        Generator::random2_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `random2` with the `rng`.
+    pub fn random2_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [-1; 8];
         let co = [-1; 8];
         let ep = [-1; 12];
         let eo = [-1; 12];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     /// Generate a cross solved state CubieCube.
     pub fn corss_solved() -> CubieCube {
+        Generator::corss_solved_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `corss_solved` with the `rng`.
+    pub fn corss_solved_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [Generator::RANDOM; 8];
         let co = [Generator::RANDOM; 8];
         let ep = [-1, -1, -1, -1, 4, 5, 6, 7, -1, -1, -1, -1];
         let eo = [-1, -1, -1, -1, 0, 0, 0, 0, -1, -1, -1, -1];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
-    /// Generate a CFOP F2L Solved(OLL) state CubieCube.
+    /// Generate a CFOP F2L Solved(OLL) state CubieCube, i.e, a random last layer.
     pub fn f2l_solved() -> CubieCube {
+        Generator::f2l_solved_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `f2l_solved` with the `rng`.
+    pub fn f2l_solved_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [-1, -1, -1, -1, 4, 5, 6, 7];
         let co = [-1, -1, -1, -1, 0, 0, 0, 0];
         let ep = [-1, -1, -1, -1, 4, 5, 6, 7, 8, 9, 10, 11];
         let eo = [-1, -1, -1, -1, 0, 0, 0, 0, 0, 0, 0, 0];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     /// Generate a CFOP OLL solved(PLL) state CubieCube.
     pub fn pll() -> CubieCube {
+        Generator::pll_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `pll` with the `rng`.
+    pub fn pll_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [-1, -1, -1, -1, 4, 5, 6, 7];
         let co = [0; 8];
         let ep = [-1, -1, -1, -1, 4, 5, 6, 7, 8, 9, 10, 11];
         let eo = [0; 12];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     pub fn lastslot() -> CubieCube {
+        Generator::lastslot_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `lastslot` with the `rng`.
+    pub fn lastslot_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [-1, -1, -1, -1, -1, 5, 6, 7];
         let co = [-1, -1, -1, -1, -1, 0, 0, 0];
         let ep = [-1, -1, -1, -1, 4, 5, 6, 7, -1, 9, 10, 11];
         let eo = [-1, -1, -1, -1, 0, 0, 0, 0, -1, 0, 0, 0];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     pub fn edge_ll() -> CubieCube {
+        Generator::edge_ll_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `edge_ll` with the `rng`.
+    pub fn edge_ll_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [Generator::SOLVED; 8];
         let co = [0; 8];
         let ep = [-1, -1, -1, -1, 4, 5, 6, 7, 8, 9, 10, 11];
         let eo = [-1, -1, -1, -1, 0, 0, 0, 0, 0, 0, 0, 0];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     pub fn corner_ll() -> CubieCube {
+        Generator::corner_ll_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `corner_ll` with the `rng`.
+    pub fn corner_ll_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [-1, -1, -1, -1, 4, 5, 6, 7];
         let co = [-1, -1, -1, -1, 0, 0, 0, 0];
         let ep = [Generator::SOLVED; 12];
         let eo = [0; 12];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     pub fn superflip() -> CubieCube {
+        Generator::superflip_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `superflip` with the `rng`.
+    pub fn superflip_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [Generator::SOLVED; 8];
         let co = [0; 8];
         let ep = [Generator::SOLVED; 12];
         let eo = [1; 12];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     pub fn roux_fb_solved() -> CubieCube {
+        Generator::roux_fb_solved_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `roux_fb_solved` with the `rng`.
+    pub fn roux_fb_solved_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [-1, -1, -1, -1, -1, 5, 6, -1];
         let co = [-1, -1, -1, -1, -1, 0, 0, -1];
         let ep = [-1, -1, -1, -1, -1, -1, 6, -1, -1, 9, 10, -1];
         let eo = [-1, -1, -1, -1, -1, -1, 0, -1, -1, 0, 0, -1];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
     }
 
     pub fn roux_fb_sb_solved() -> CubieCube {
+        Generator::roux_fb_sb_solved_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `roux_fb_sb_solved` with the `rng`.
+    pub fn roux_fb_sb_solved_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
         let cp = [-1, -1, -1, -1, 4, 5, 6, 7];
         let co = [-1, -1, -1, -1, 0, 0, 0, 0];
         let ep = [-1, -1, -1, -1, 4, -1, 6, -1, 8, 9, 10, 11];
         let eo = [-1, -1, -1, -1, 0, -1, 0, -1, 0, 0, 0, 0];
-        Generator::gen_state(cp, co, ep, eo)
+        Generator::gen_state_with_rng(cp, co, ep, eo, rng)
+    }
+
+    /// Generate a random <R, U> state(2-gen, eg. for one-handed), uniform in the group.
+    pub fn two_gen() -> CubieCube {
+        Generator::two_gen_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `two_gen` with the `rng`.
+    pub fn two_gen_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
+        let perms = Generator::two_gen_corners();
+        let mut cc = CubieCube {
+            cp: perms[rng.gen_range(0..perms.len() as u32) as usize],
+            ..CubieCube::default()
+        };
+        Generator::twist(&mut cc.co, &[0, 1, 2, 3, 4, 7], 3, rng);
+        let edges = [0, 1, 2, 3, 4, 8, 11];
+        Generator::shuffle(&mut cc.ep, &edges, rng);
+        if cc.corner_parity() != cc.edge_parity() {
+            cc.ep.swap(edges[0], edges[1]);
+        }
+        cc
+    }
+
+    /// Generate a random <R, U, F> state(3-gen), i.e, the DBL corner and the DL, DB, BL edges are solved.
+    pub fn three_gen() -> CubieCube {
+        Generator::three_gen_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `three_gen` with the `rng`.
+    pub fn three_gen_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
+        let mut cc = CubieCube::default();
+        let corners = [0, 1, 2, 3, 4, 5, 7];
+        Generator::shuffle(&mut cc.cp, &corners, rng);
+        Generator::twist(&mut cc.co, &corners, 3, rng);
+        let edges = [0, 1, 2, 3, 4, 5, 8, 9, 11];
+        Generator::shuffle(&mut cc.ep, &edges, rng);
+        Generator::twist(&mut cc.eo, &edges, 2, rng);
+        if cc.corner_parity() != cc.edge_parity() {
+            cc.ep.swap(edges[0], edges[1]);
+        }
+        cc
+    }
+
+    /// Generate a random <M, U> state(Roux LSE), the M slice and the U layer may be unaligned.
+    pub fn lse() -> CubieCube {
+        Generator::lse_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `lse` with the `rng`.
+    pub fn lse_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
+        // The group is the U and M turns times the even permutations and flips of the 6 edges.
+        let mut edges = CubieCube::default();
+        let positions = [0, 1, 2, 3, 5, 7];
+        Generator::shuffle(&mut edges.ep, &positions, rng);
+        if !edges.edge_parity() {
+            edges.ep.swap(positions[0], positions[1]);
+        }
+        Generator::twist(&mut edges.eo, &positions, 2, rng);
+        let m = [Move::N, Move::M, Move::M2, Move::M3][rng.gen_range(0..4u32) as usize];
+        CubieCube::default()
+            .apply_move(Generator::auf(rng))
+            .apply_move(m)
+            * edges
+    }
+
+    /// Generate a random EO solved state(ZZ), i.e, all edges are oriented.
+    pub fn eo_solved() -> CubieCube {
+        Generator::eo_solved_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `eo_solved` with the `rng`.
+    pub fn eo_solved_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
+        let mut cc = CubieCube::default();
+        Generator::shuffle(&mut cc.cp, &[0, 1, 2, 3, 4, 5, 6, 7], rng);
+        Generator::twist(&mut cc.co, &[0, 1, 2, 3, 4, 5, 6, 7], 3, rng);
+        Generator::shuffle(&mut cc.ep, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], rng);
+        if cc.corner_parity() != cc.edge_parity() {
+            cc.ep.swap(0, 1);
+        }
+        cc
+    }
+
+    /// Generate a random <U, D, R2, L2, F2, B2> state(domino), i.e, all pieces are oriented and the E slice edges are in the E slice.
+    pub fn domino() -> CubieCube {
+        Generator::domino_with_rng(&mut rand::thread_rng())
+    }
+
+    /// `domino` with the `rng`.
+    pub fn domino_with_rng<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
+        let mut cc = CubieCube::default();
+        Generator::shuffle(&mut cc.cp, &[0, 1, 2, 3, 4, 5, 6, 7], rng);
+        Generator::shuffle(&mut cc.ep, &[0, 1, 2, 3, 4, 5, 6, 7], rng);
+        Generator::shuffle(&mut cc.ep, &[8, 9, 10, 11], rng);
+        if cc.corner_parity() != cc.edge_parity() {
+            cc.ep.swap(8, 9);
+        }
+        cc
+    }
+
+    /// Generate a state of the OLL case `number`(1-57) with a random AUF and a random permutation of the last layer.
+    pub fn oll_case(number: u8) -> Result<CubieCube, Error> {
+        Generator::oll_case_with_rng(number, &mut rand::thread_rng())
+    }
+
+    /// `oll_case` with the `rng`.
+    pub fn oll_case_with_rng<R: Rng + ?Sized>(number: u8, rng: &mut R) -> Result<CubieCube, Error> {
+        let algorithm = OLLSolver::new(CubieCube::default())
+            .algorithm(number)
+            .ok_or(Error::InvalidCase {
+                set: "OLL",
+                case: number.to_string(),
+            })?;
+        let cc = Generator::unsolve(Generator::pll_with_rng(rng), algorithm);
+        Ok(cc.apply_move(Generator::auf(rng)))
+    }
+
+    /// Generate a state of the PLL case `name`, eg. "Ua", with random AUFs before and after.
+    pub fn pll_case(name: &str) -> Result<CubieCube, Error> {
+        Generator::pll_case_with_rng(name, &mut rand::thread_rng())
+    }
+
+    /// `pll_case` with the `rng`.
+    pub fn pll_case_with_rng<R: Rng + ?Sized>(name: &str, rng: &mut R) -> Result<CubieCube, Error> {
+        let algorithm =
+            PLLSolver::new(CubieCube::default())
+                .algorithm(name)
+                .ok_or(Error::InvalidCase {
+                    set: "PLL",
+                    case: name.to_string(),
+                })?;
+        let cc = CubieCube::default().apply_move(Generator::auf(rng));
+        Ok(Generator::unsolve(cc, algorithm).apply_move(Generator::auf(rng)))
+    }
+
+    /// Generate a state of the CMLL case `name`, eg. "pi_right_bar", with random AUFs and a random LSE.
+    pub fn cmll_case(name: &str) -> Result<CubieCube, Error> {
+        Generator::cmll_case_with_rng(name, &mut rand::thread_rng())
+    }
+
+    /// `cmll_case` with the `rng`.
+    pub fn cmll_case_with_rng<R: Rng + ?Sized>(
+        name: &str,
+        rng: &mut R,
+    ) -> Result<CubieCube, Error> {
+        let algorithm = CMLLSolver::new(CubieCube::default())
+            .algorithm(name)
+            .ok_or(Error::InvalidCase {
+                set: "CMLL",
+                case: name.to_string(),
+            })?;
+        let cc = Generator::unsolve(Generator::lse_with_rng(rng), algorithm);
+        Ok(cc.apply_move(Generator::auf(rng)))
+    }
+
+    /// Generate a state of the F2L case `id` for the `slot`(Edge::FR, FL, BL or BR), the cross and the other slots are solved.
+    ///
+    /// The 41 cases are numbered by the places of the corner and the edge: 1-24 both in the U layer,
+    /// 25-30 the corner in the slot, 31-36 the edge in the slot, 37-41 both in the slot.
+    pub fn f2l_case(slot: Edge, id: usize) -> Result<CubieCube, Error> {
+        Generator::f2l_case_with_rng(slot, id, &mut rand::thread_rng())
+    }
+
+    /// `f2l_case` with the `rng`.
+    pub fn f2l_case_with_rng<R: Rng + ?Sized>(
+        slot: Edge,
+        id: usize,
+        rng: &mut R,
+    ) -> Result<CubieCube, Error> {
+        let invalid = || Error::InvalidCase {
+            set: "F2L",
+            case: format!("{:?} {}", slot, id),
+        };
+        let turns = match slot {
+            Edge::FR => 0,
+            Edge::FL => 1,
+            Edge::BL => 2,
+            Edge::BR => 3,
+            _ => return Err(invalid()),
+        };
+        let cases = Generator::f2l_cases();
+        let [(c, co), (e, eo)] = *id
+            .checked_sub(1)
+            .and_then(|i| cases.get(i))
+            .ok_or_else(invalid)?;
+        let mut cpv = [-1, -1, -1, -1, -1, 5, 6, 7];
+        let mut cov = [-1, -1, -1, -1, -1, 0, 0, 0];
+        let mut epv = [-1, -1, -1, -1, 4, 5, 6, 7, -1, 9, 10, 11];
+        let mut eov = [-1, -1, -1, -1, 0, 0, 0, 0, -1, 0, 0, 0];
+        let (c, e) = (if c < 4 { c } else { 4 }, if e < 4 { e } else { 8 });
+        cpv[c] = Corner::DFR as i8;
+        cov[c] = co as i8;
+        epv[e] = Edge::FR as i8;
+        eov[e] = eo as i8;
+        let cc =
+            Generator::gen_state_with_rng(cpv, cov, epv, eov, rng).apply_move(Generator::auf(rng));
+        // Move the front right slot to the `slot` by the conjugation with a y rotation.
+        let y = CubieCube::default().apply_move(Move::y);
+        let mut r = CubieCube::default();
+        for _ in 0..turns {
+            r = r * y;
+        }
+        Ok(r.inverse_cubie_cube() * cc * r)
+    }
+
+    /// Generate a state of the ZBLL case `id` of the OCLL `oll`("H", "Pi", "U", "T", "L", "Sune", "Antisune") with random AUFs,
+    /// the cases are numbered from 1, see `zbll_count`.
+    pub fn zbll_case(oll: &str, id: usize) -> Result<CubieCube, Error> {
+        Generator::zbll_case_with_rng(oll, id, &mut rand::thread_rng())
+    }
+
+    /// `zbll_case` with the `rng`.
+    pub fn zbll_case_with_rng<R: Rng + ?Sized>(
+        oll: &str,
+        id: usize,
+        rng: &mut R,
+    ) -> Result<CubieCube, Error> {
+        let cases = Generator::zbll_cases(oll)?;
+        let cc = *id
+            .checked_sub(1)
+            .and_then(|i| cases.get(i))
+            .ok_or(Error::InvalidCase {
+                set: "ZBLL",
+                case: format!("{} {}", oll, id),
+            })?;
+        let cc = CubieCube::default().apply_move(Generator::auf(rng)) * cc;
+        Ok(cc.apply_move(Generator::auf(rng)))
+    }
+
+    /// The number of ZBLL cases of the OCLL `oll`, 40 for "H" and 72 for the others.
+    pub fn zbll_count(oll: &str) -> Result<usize, Error> {
+        Ok(Generator::zbll_cases(oll)?.len())
+    }
+
+    /// Generate a state exactly `n` moves from solved in the `metric`(HTM or QTM), certified by an optimal search.
+    ///
+    /// The states are random walks of `n` moves, without the walks found shorter, i.e, close to uniform.
+    /// HTM is searched by min2phase, QTM by a search meeting the states near solved, practical for `n` up to about 12.
+    /// No state is further than God's number, 20 in HTM and 26 in QTM, an error is returned beyond it.
+    pub fn at_distance(n: usize, metric: Metric) -> Result<CubieCube, Error> {
+        Generator::at_distance_with_rng(n, metric, &mut rand::thread_rng())
+    }
+
+    /// `at_distance` with the `rng`.
+    pub fn at_distance_with_rng<R: Rng + ?Sized>(
+        n: usize,
+        metric: Metric,
+        rng: &mut R,
+    ) -> Result<CubieCube, Error> {
+        let (moves, max): (&[Move], usize) = match metric {
+            Metric::HTM => (&ALL_MOVES, 20),
+            Metric::QTM => (&QUARTER_TURNS, 26),
+            _ => return Err(Error::UnsupportedMetric),
+        };
+        if n > max {
+            return Err(Error::InvalidDistance { distance: n, max });
+        }
+        // Moves of a face: 3 in HTM, 2 in QTM.
+        let per_face = moves.len() / 6;
+        loop {
+            let mut cc = CubieCube::default();
+            let mut walk: Vec<usize> = Vec::new();
+            while walk.len() < n {
+                let i = rng.gen_range(0..moves.len() as u32) as usize;
+                let face = i / per_face;
+                let last = walk.last().map(|j| j / per_face);
+                // A half turn is two same quarter turns in QTM.
+                let repeat = metric == Metric::QTM
+                    && walk.last() == Some(&i)
+                    && walk.iter().rev().nth(1) != Some(&i);
+                // Opposite faces in a fixed order, no other turn of the last face.
+                if last == Some(face + 3) || (last == Some(face) && !repeat) {
+                    continue;
+                }
+                cc = cc.apply_move(moves[i]);
+                walk.push(i);
+            }
+            if Generator::is_at_distance(&cc, n, metric) {
+                return Ok(cc);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{facelet::FaceCube, printer::print_facelet};
+    use std::collections::HashSet;
+
+    use rand_chacha::ChaCha8Rng;
+
+    use super::QTM_BALL;
+    use crate::{
+        cubie::{Corner, CubieCube, Edge},
+        error::Error,
+        facelet::FaceCube,
+        moves::{Formula, Metric, Move},
+        printer::print_facelet,
+        recognition,
+        solver::min2phase::{constants::OPTIMAL_SOLUTION, solver::Solver},
+    };
 
     use super::Generator;
 
@@ -268,4 +871,271 @@ mod tests {
         println!("{}", fc.to_string());
         let _ = print_facelet(&fc);
     }
+
+    #[test]
+    fn test_seeded() {
+        let helpers: [fn(&mut ChaCha8Rng) -> CubieCube; 15] = [
+            Generator::random2_with_rng,
+            Generator::corss_solved_with_rng,
+            Generator::f2l_solved_with_rng,
+            Generator::pll_with_rng,
+            Generator::lastslot_with_rng,
+            Generator::edge_ll_with_rng,
+            Generator::corner_ll_with_rng,
+            Generator::superflip_with_rng,
+            Generator::roux_fb_solved_with_rng,
+            Generator::roux_fb_sb_solved_with_rng,
+            Generator::two_gen_with_rng,
+            Generator::three_gen_with_rng,
+            Generator::lse_with_rng,
+            Generator::eo_solved_with_rng,
+            Generator::domino_with_rng,
+        ];
+        for f in helpers {
+            let cc = f(&mut Generator::seeded_rng(1));
+            assert_eq!(f(&mut Generator::seeded_rng(1)), cc);
+            assert!(cc.center_parity() == (cc.corner_parity() == cc.edge_parity()));
+        }
+        let mut rng = Generator::seeded_rng(1);
+        let cc = Generator::random_with_rng(&mut rng);
+        assert_ne!(Generator::random_with_rng(&mut rng), cc);
+        assert_eq!(
+            Generator::random_with_rng(&mut Generator::seeded_rng(1)),
+            cc
+        );
+        assert_ne!(
+            Generator::random2_with_rng(&mut Generator::seeded_rng(2)),
+            Generator::random2_with_rng(&mut Generator::seeded_rng(3))
+        );
+
+        // Golden values, the same on every platform.
+        let fc = FaceCube::try_from(&Generator::random_with_rng(&mut Generator::seeded_rng(
+            2024,
+        )))
+        .unwrap();
+        assert_eq!(
+            fc.to_string(),
+            "DDFDURUBLUFLURLDUURUFBFFBDLULBLDRBLRFFFDLRRFLDRRBBBBUD"
+        );
+        let s = Formula::scramble_with_rng(&mut Generator::seeded_rng(2024));
+        assert_eq!(
+            s.to_string(),
+            "L F' L B D' R' B L' F' L F D' R2 L2 R' D2 L2 B2"
+        );
+    }
+
+    #[test]
+    fn test_subsets() {
+        // Breadth first search of the <M, U> group.
+        let mut lse = vec![CubieCube::default()];
+        let mut seen = HashSet::from([format!("{:?}", lse[0])]);
+        let mut i = 0;
+        while i < lse.len() {
+            for m in [Move::M, Move::U] {
+                let cc = lse[i].apply_move(m);
+                if seen.insert(format!("{:?}", cc)) {
+                    lse.push(cc);
+                }
+            }
+            i += 1;
+        }
+        assert_eq!(lse.len(), 184320);
+        assert_eq!(Generator::two_gen_corners().len(), 120);
+
+        let mut rng = Generator::seeded_rng(5);
+        let corners = Generator::two_gen_corners();
+        for _ in 0..20 {
+            assert!(seen.contains(&format!("{:?}", Generator::lse_with_rng(&mut rng))));
+
+            let cc = Generator::two_gen_with_rng(&mut rng);
+            assert!(cc.verify().is_ok() && corners.contains(&cc.cp));
+            assert!(cc.eo == [0; 12] && cc.ep[5] == Edge::DF && cc.co[5] == 0);
+
+            let cc = Generator::three_gen_with_rng(&mut rng);
+            assert!(cc.verify().is_ok() && cc.cp[6] == Corner::DBL && cc.ep[10] == Edge::BL);
+
+            let cc = Generator::eo_solved_with_rng(&mut rng);
+            assert!(cc.verify().is_ok() && cc.eo == [0; 12]);
+
+            let cc = Generator::domino_with_rng(&mut rng);
+            assert!(cc.verify().is_ok() && cc.eo == [0; 12] && cc.co == [0; 8]);
+            assert!(cc.ep[8..].iter().all(|e| *e as usize >= 8));
+        }
+    }
+
+    #[test]
+    fn test_subset_distribution() {
+        // The free pieces are at every free position with every orientation equally often,
+        // with both parities, the other pieces are solved.
+        fn check(states: &[CubieCube], corners: &[usize], edges: &[usize], flips: bool) {
+            let share = |f: &dyn Fn(&CubieCube) -> bool| {
+                states.iter().filter(|cc| f(cc)).count() as f64 / states.len() as f64
+            };
+            let near = |x: f64, expected: f64| (x / expected - 1.0).abs() < 0.2;
+            for cc in states {
+                assert!(cc.verify().is_ok());
+                assert!((0..8)
+                    .all(|i| corners.contains(&i) || (cc.cp[i] as usize == i && cc.co[i] == 0)));
+                assert!((0..12)
+                    .all(|i| edges.contains(&i) || (cc.ep[i] as usize == i && cc.eo[i] == 0)));
+                assert!(flips || cc.eo == [0; 12]);
+            }
+            for p in corners {
+                for c in corners {
+                    assert!(near(
+                        share(&|cc| cc.cp[*p] as usize == *c),
+                        1.0 / corners.len() as f64
+                    ));
+                }
+                for o in 0..3 {
+                    assert!(near(share(&|cc| cc.co[*p] == o), 1.0 / 3.0));
+                }
+            }
+            for p in edges {
+                for e in edges {
+                    assert!(near(
+                        share(&|cc| cc.ep[*p] as usize == *e),
+                        1.0 / edges.len() as f64
+                    ));
+                }
+                if flips {
+                    assert!(near(share(&|cc| cc.eo[*p] == 1), 0.5));
+                }
+            }
+            assert!(near(share(&|cc| cc.corner_parity()), 0.5));
+        }
+
+        let mut rng = Generator::seeded_rng(12);
+        let states: Vec<CubieCube> = (0..6000)
+            .map(|_| Generator::three_gen_with_rng(&mut rng))
+            .collect();
+        check(
+            &states,
+            &[0, 1, 2, 3, 4, 5, 7],
+            &[0, 1, 2, 3, 4, 5, 8, 9, 11],
+            true,
+        );
+        let states: Vec<CubieCube> = (0..6000)
+            .map(|_| Generator::eo_solved_with_rng(&mut rng))
+            .collect();
+        check(
+            &states,
+            &[0, 1, 2, 3, 4, 5, 6, 7],
+            &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_cases() {
+        let mut rng = Generator::seeded_rng(9);
+        for number in 1..=57 {
+            let cc = Generator::oll_case_with_rng(number, &mut rng).unwrap();
+            assert_eq!(recognition::oll(&cc).unwrap().name, number.to_string());
+        }
+        assert!(Generator::oll_case(58).is_err());
+        for name in [
+            "Aa", "E", "Gc", "H", "Jb", "Na", "Rb", "T", "Ua", "V", "Y", "Z",
+        ] {
+            let cc = Generator::pll_case_with_rng(name, &mut rng).unwrap();
+            assert_eq!(recognition::pll(&cc).unwrap().name, name);
+        }
+        assert!(Generator::pll_case("Q").is_err());
+        for name in [
+            "o_adjacent_swap",
+            "h_rows",
+            "pi_right_bar",
+            "u_back_row",
+            "t_left_bar",
+            "s_columns",
+            "l_pure",
+        ] {
+            let cc = Generator::cmll_case_with_rng(name, &mut rng).unwrap();
+            assert_eq!(recognition::cmll(&cc).unwrap().name, name);
+        }
+
+        assert_eq!(Generator::f2l_cases().len(), 41);
+        let slots = [
+            (Edge::FR, Corner::DFR),
+            (Edge::FL, Corner::DLF),
+            (Edge::BL, Corner::DBL),
+            (Edge::BR, Corner::DRB),
+        ];
+        for (slot, corner) in slots {
+            for id in 1..=41 {
+                let cc = Generator::f2l_case_with_rng(slot, id, &mut rng).unwrap();
+                assert!(cc.verify().is_ok());
+                let solved = |(e, c): (Edge, Corner)| {
+                    cc.cp[c as usize] == c
+                        && cc.co[c as usize] == 0
+                        && cc.ep[e as usize] == e
+                        && cc.eo[e as usize] == 0
+                };
+                assert!(!solved((slot, corner)));
+                assert!(slots.iter().all(|s| s.0 == slot || solved(*s)));
+                assert!((4..8).all(|e| cc.ep[e] as usize == e && cc.eo[e] == 0));
+            }
+        }
+        assert!(Generator::f2l_case(Edge::UR, 1).is_err());
+        assert!(Generator::f2l_case(Edge::FR, 42).is_err());
+
+        let counts: Vec<usize> = ["H", "Pi", "U", "T", "L", "Sune", "Antisune"]
+            .iter()
+            .map(|oll| Generator::zbll_count(oll).unwrap())
+            .collect();
+        assert_eq!(counts, [40, 72, 72, 72, 72, 72, 72]);
+        for id in 1..=72 {
+            let cc = Generator::zbll_case_with_rng("T", id, &mut rng).unwrap();
+            assert_eq!(recognition::coll(&cc).unwrap().name, "T");
+        }
+        assert!(Generator::zbll_case("T", 73).is_err());
+    }
+
+    #[test]
+    fn test_at_distance() {
+        let mut rng = Generator::seeded_rng(12);
+        for (metric, count) in [(Metric::HTM, 243), (Metric::QTM, 114)] {
+            let states: HashSet<u128> = (0..2000)
+                .map(|_| {
+                    Generator::at_distance_with_rng(2, metric, &mut rng)
+                        .unwrap()
+                        .index()
+                })
+                .collect();
+            assert_eq!(states.len(), count);
+        }
+        assert_eq!(
+            Generator::at_distance(0, Metric::HTM).unwrap(),
+            CubieCube::default()
+        );
+        for n in 1..=6 {
+            let cc = Generator::at_distance_with_rng(n, Metric::QTM, &mut rng).unwrap();
+            assert_eq!(QTM_BALL[&cc.index()] as usize, n);
+        }
+        let cc = Generator::at_distance_with_rng(10, Metric::QTM, &mut rng).unwrap();
+        assert!(!Generator::qtm_within(&cc, 8) && Generator::qtm_within(&cc, 10));
+        for n in [3, 7, 10] {
+            let cc = Generator::at_distance_with_rng(n, Metric::HTM, &mut rng).unwrap();
+            let facelet = FaceCube::try_from(&cc).unwrap().to_string();
+            let solution = Solver::default()
+                .solve(&facelet, 20, u64::MAX, 0, OPTIMAL_SOLUTION)
+                .unwrap();
+            assert_eq!(solution.moves.len(), n);
+        }
+        assert!(Generator::at_distance(3, Metric::STM).is_err());
+        assert!(matches!(
+            Generator::at_distance(21, Metric::HTM),
+            Err(Error::InvalidDistance {
+                distance: 21,
+                max: 20
+            })
+        ));
+        assert!(matches!(
+            Generator::at_distance(27, Metric::QTM),
+            Err(Error::InvalidDistance {
+                distance: 27,
+                max: 26
+            })
+        ));
+    }
 }
@@ -7,14 +7,32 @@ pub mod error;
 pub mod constants;
 /// Module for represent a cube on the facelet level.
 pub mod facelet;
+/// Module for the colour schemes, mapping the sticker colours to the faces.
+pub mod scheme;
 /// Module for represent a cube on the cubie level.
 pub mod cubie;
+/// Module for the 48 symmetries of the cube(rotations and reflections).
+pub mod symmetry;
+/// Module for the interchange formats(Reid, cubing.js and kociemba).
+pub mod formats;
+/// Module for the validation of the cube states, with piece level diagnostics and fixes.
+pub mod validation;
 /// Module for represent moves.
 pub mod moves;
+/// Module for algorithms with structure(commutators, conjugates, repeated groups).
+pub mod alg;
 /// Module for generator.(generate a random cube, a defined state, eg. cross.)
 pub mod generator;
+/// Module for the textual constraint spec of the generated states, with uniform sampling and counting.
+pub mod spec;
+/// Module for the scramble sets of the 3x3x3 events of a competition(333, 333bf, 333fm), exported as JSON with serde.
+pub mod competition;
 /// Module for Solvers.
 pub mod solver;
+/// Module for recognise the cases of the last layer(OLL, PLL, CMLL, COLL, EPLL).
+pub mod recognition;
+/// Module for check if a step(cross, blocks, EO) is solved in any orientation.
+pub mod predicates;
 #[cfg(feature = "term")]
 /// Module for print a facelet cube on terminal witch color.
 pub mod printer;
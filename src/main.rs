@@ -2,7 +2,7 @@ use std::time::Instant;
 
 use rcuber::cubie::CubieCube;
 use rcuber::facelet::FaceCube;
-use rcuber::moves::Formula;
+use rcuber::moves::{Formula, Metric};
 #[allow(unused_imports)]
 use rcuber::moves::Move::*;
 #[cfg(feature = "term")]
@@ -11,58 +11,37 @@ use rcuber::solver::cfop::CFOPSolver;
 use rcuber::solver::lbl::LBLSolver;
 use rcuber::solver::roux::RouxSolver;
 use rcuber::solver::Min2PhaseSolver;
+use rcuber::solver::Solver;
 
 fn main() {
     let cc = CubieCube::default();
     let moves = Formula::scramble();
     println!("Scramble: {:?}", moves);
     let cc = cc.apply_formula(&moves);
-    let cc2 = cc.clone();
-    let cc3 = cc.clone();
-    let cc4 = cc.clone();
     let fc = FaceCube::try_from(&cc).unwrap();
     let _r = print_facelet(&fc);
-    let start = Instant::now();
-    let mut lbl = LBLSolver { cube: cc };
-    let lbls = lbl.solve();
-    assert!(lbl.is_solved());
-    let lble = start.elapsed();
-    println!(
-        "LBL Solution: {:?}, Len: {}, Time: {:?}",
-        lbls,
-        lbls.len(),
-        lble
-    );
-    let start = Instant::now();
-    let mut cfop = CFOPSolver { cube: cc2 };
-    let cfops = cfop.solve();
-    let cfope = start.elapsed();
-    println!(
-        "CFOP Solution: {:?}, Len: {}, Time: {:?}",
-        cfops,
-        cfops.len(),
-        cfope
-    );
-    let start = Instant::now();
-    let mut roux = RouxSolver { cube: cc3 };
-    let rouxs = roux.solve();
-    let rouxe = start.elapsed();
-    println!(
-        "Roux Solution: {:?}, Len: {}, Time: {:?}",
-        rouxs,
-        rouxs.len(),
-        rouxe
-    );
-    let start = Instant::now();
-    let mut m2p = Min2PhaseSolver { cube: cc4 };
-    let m2ps = m2p.solve();
-    let m2pe = start.elapsed();
-    println!(
-        "Min2Phase Solution: {}, Len: {}, Time: {:?}",
-        m2ps,
-        m2ps.moves.len(),
-        m2pe
-    );
+    let solvers: Vec<Box<dyn Solver>> = vec![
+        Box::new(LBLSolver::new(cc)),
+        Box::new(CFOPSolver::new(cc)),
+        Box::new(RouxSolver::new(cc)),
+        Box::new(Min2PhaseSolver::new(cc)),
+    ];
+    for mut solver in solvers {
+        let start = Instant::now();
+        let solution = solver.solve();
+        let elapsed = start.elapsed();
+        match solution {
+            Ok(s) => println!(
+                "{} Solution: {}, Len: {} HTM / {} STM, Time: {:?}",
+                solver.name(),
+                s,
+                s.count(Metric::HTM),
+                s.count(Metric::STM),
+                elapsed
+            ),
+            Err(e) => println!("{} Error: {}", solver.name(), e),
+        }
+    }
     // let fc = FaceCube::try_from(&lbl.cube).unwrap();
     // let _r = print_facelet(&fc);
 }
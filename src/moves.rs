@@ -2,12 +2,17 @@ use std::fmt::Display;
 use std::ops::{Add, Mul};
 use std::{fmt, str::FromStr};
 
-use rand::random;
+use rand::{Rng, RngCore};
+use static_init::dynamic;
+use std::collections::HashMap;
 
 use self::Move::*;
-use crate::cubie::{Corner::*, CubieCube, Edge::*};
+use crate::constants::{ALL_COLORS, ALL_MOVES, ALL_MOVES_FULL};
+use crate::cubie::{Corner::*, CubieCube, Edge::*, SOLVED_CUBIE_CUBE};
 use crate::error::Error;
-use crate::facelet::Color;
+use crate::facelet::{Color, FaceCube};
+use crate::generator::Generator;
+use crate::solver::min2phase::solver::Solver;
 
 /// Face Turns Moves: Up, Right, Front, Down, Left, Back; 
 /// Slice Moves: Slice moves only turn the middle layer, M follows the L direction, E follows the D direction, S follows the F direction.
@@ -44,6 +49,22 @@ pub enum Move {
     N,
 }
 
+/// Serialize as the WCA notation, eg. "R'".
+#[cfg(feature = "serde")]
+impl serde::Serialize for Move {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Move {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Move::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -73,63 +94,190 @@ impl fmt::Display for Move {
 impl FromStr for Move {
     type Err = Error;
 
+    /// Parse a single move in WCA notation, eg. "R", "U'", "F2", "Rw'", "r", "R2'", "R3".
+    /// "N", the display of the empty move, is parsed as well.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "R" => Ok(R),
-            "R'" => Ok(R3),
-            "R2" => Ok(R2),
-            "L" => Ok(L),
-            "L'" => Ok(L3),
-            "L2" => Ok(L2),
-            "U" => Ok(U),
-            "U'" => Ok(U3),
-            "U2" => Ok(U2),
-            "D" => Ok(D),
-            "D'" => Ok(D3),
-            "D2" => Ok(D2),
-            "F" => Ok(F),
-            "F'" => Ok(F3),
-            "F2" => Ok(F2),
-            "B" => Ok(B),
-            "B'" => Ok(B3),
-            "B2" => Ok(B2),
-            "M" => Ok(M),
-            "M'" => Ok(M3),
-            "M2" => Ok(M2),
-            "E" => Ok(E),
-            "E'" => Ok(E3),
-            "E2" => Ok(E2),
-            "S" => Ok(S),
-            "S'" => Ok(S3),
-            "S2" => Ok(S2),
-            "Rw" => Ok(Rw),
-            "Rw'" => Ok(Rw3),
-            "Rw2" => Ok(Rw2),
-            "Lw" => Ok(Lw),
-            "Lw'" => Ok(Lw3),
-            "Lw2" => Ok(Lw2),
-            "Uw" => Ok(Uw),
-            "Uw'" => Ok(Uw3),
-            "Uw2" => Ok(Uw2),
-            "Dw" => Ok(Dw),
-            "Dw'" => Ok(Dw3),
-            "Dw2" => Ok(Dw2),
-            "Fw" => Ok(Fw),
-            "Fw'" => Ok(Fw3),
-            "Fw2" => Ok(Fw2),
-            "Bw" => Ok(Bw),
-            "Bw'" => Ok(Bw3),
-            "Bw2" => Ok(Bw2),
-            "x" => Ok(x),
-            "x'" => Ok(x3),
-            "x2" => Ok(x2),
-            "y" => Ok(y),
-            "y'" => Ok(y3),
-            "y2" => Ok(y2),
-            "z" => Ok(z),
-            "z'" => Ok(z3),
-            "z2" => Ok(z2),
-            _ => Err(Error::InvalidScramble),
+        if s == "N" {
+            return Ok(N);
+        }
+        let mut parser = Parser::new(s);
+        match parser.single()? {
+            Some(m) if parser.peek().is_none() => Ok(m),
+            _ => Err(parser.error(0)),
+        }
+    }
+}
+
+/// Maximal repetition of a group, eg. "(R U)6".
+const MAX_REPEAT: usize = 100;
+
+/// Maximal nesting of groups, eg. 2 for "((R U)2 F)3".
+const MAX_DEPTH: usize = 32;
+
+/// Maximal number of moves once the groups are expanded.
+pub(crate) const MAX_MOVES: usize = 10_000;
+
+/// Parser of the WCA notation, see `Formula::from_string`.
+pub(crate) struct Parser {
+    pub(crate) chars: Vec<char>,
+    pub(crate) pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    pub(crate) fn new(s: &str) -> Self {
+        Self {
+            chars: s.chars().collect(),
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Error for the token starting at `start`.
+    pub(crate) fn error(&self, start: usize) -> Error {
+        let token = self.chars[start..]
+            .iter()
+            .take_while(|c| !c.is_whitespace())
+            .collect();
+        Error::InvalidScramble {
+            position: start,
+            token,
+        }
+    }
+
+    /// Skip whitespaces and `//` comments.
+    pub(crate) fn skip(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.pos += 1,
+                Some('/') if self.chars.get(self.pos + 1) == Some(&'/') => {
+                    while !matches!(self.peek(), None | Some('\n')) {
+                        self.pos += 1;
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Parse a number, eg. the amount of a move or the repetition of a group.
+    pub(crate) fn number(&mut self) -> Result<Option<usize>, Error> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        let n: String = self.chars[start..self.pos].iter().collect();
+        n.parse().map(Some).map_err(|_| self.error(start))
+    }
+
+    /// Enter the group opened at `start`, at most `MAX_DEPTH` groups are nested.
+    pub(crate) fn open(&mut self, start: usize) -> Result<(), Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(start));
+        }
+        self.depth += 1;
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Leave the group opened at `start` with the closing character `c`.
+    pub(crate) fn close(&mut self, start: usize, c: char) -> Result<(), Error> {
+        if self.peek() != Some(c) {
+            return Err(self.error(start));
+        }
+        self.depth -= 1;
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Parse the repetition of a group, 1 if none, at most `MAX_REPEAT`.
+    pub(crate) fn repeat(&mut self) -> Result<usize, Error> {
+        let start = self.pos;
+        match self.number()? {
+            Some(n) if n > MAX_REPEAT => Err(self.error(start)),
+            n => Ok(n.unwrap_or(1)),
+        }
+    }
+
+    /// Parse moves and groups until the end or a closing parenthesis.
+    fn sequence(&mut self) -> Result<Vec<Move>, Error> {
+        let mut moves = Vec::new();
+        loop {
+            self.skip();
+            match self.peek() {
+                None | Some(')') => return Ok(moves),
+                Some('(') => {
+                    let start = self.pos;
+                    self.open(start)?;
+                    let group = self.sequence()?;
+                    self.close(start, ')')?;
+                    let n = self.repeat()?;
+                    if moves.len() + group.len() * n > MAX_MOVES {
+                        return Err(self.error(start));
+                    }
+                    for _ in 0..n {
+                        moves.extend(&group);
+                    }
+                }
+                Some(_) => moves.extend(self.single()?),
+            }
+        }
+    }
+
+    /// Parse a move, returns `None` if its amount is a multiple of 4, eg. "R4".
+    pub(crate) fn single(&mut self) -> Result<Option<Move>, Error> {
+        let start = self.pos;
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return Err(self.error(start)),
+        };
+        self.pos += 1;
+        let wide = self.peek() == Some('w');
+        let turns = match c {
+            'U' if wide => [Uw, Uw2, Uw3],
+            'R' if wide => [Rw, Rw2, Rw3],
+            'F' if wide => [Fw, Fw2, Fw3],
+            'D' if wide => [Dw, Dw2, Dw3],
+            'L' if wide => [Lw, Lw2, Lw3],
+            'B' if wide => [Bw, Bw2, Bw3],
+            'U' => [U, U2, U3],
+            'R' => [R, R2, R3],
+            'F' => [F, F2, F3],
+            'D' => [D, D2, D3],
+            'L' => [L, L2, L3],
+            'B' => [B, B2, B3],
+            'M' => [M, M2, M3],
+            'E' => [E, E2, E3],
+            'S' => [S, S2, S3],
+            'u' => [Uw, Uw2, Uw3],
+            'r' => [Rw, Rw2, Rw3],
+            'f' => [Fw, Fw2, Fw3],
+            'd' => [Dw, Dw2, Dw3],
+            'l' => [Lw, Lw2, Lw3],
+            'b' => [Bw, Bw2, Bw3],
+            'x' => [x, x2, x3],
+            'y' => [y, y2, y3],
+            'z' => [z, z2, z3],
+            _ => return Err(self.error(start)),
+        };
+        if wide && c.is_ascii_uppercase() && !matches!(c, 'M' | 'E' | 'S') {
+            self.pos += 1;
+        }
+        let mut n = self.number()?.unwrap_or(1) % 4;
+        if matches!(self.peek(), Some('\'' | '\u{2019}')) {
+            self.pos += 1;
+            n = (4 - n) % 4;
+        }
+        match n {
+            0 => Ok(None),
+            n => Ok(Some(turns[n - 1])),
         }
     }
 }
@@ -251,6 +399,24 @@ impl Move {
             E3 => E,
             S => S3,
             S3 => S,
+            Uw => Uw3,
+            Uw3 => Uw,
+            Dw => Dw3,
+            Dw3 => Dw,
+            Rw => Rw3,
+            Rw3 => Rw,
+            Lw => Lw3,
+            Lw3 => Lw,
+            Fw => Fw3,
+            Fw3 => Fw,
+            Bw => Bw3,
+            Bw3 => Bw,
+            x => x3,
+            x3 => x,
+            y => y3,
+            y3 => y,
+            z => z3,
+            z3 => z,
             _ => self,
         }
     }
@@ -273,6 +439,111 @@ impl Move {
         }
     }
 
+    /// Check if the move is a cube rotation(x, y, z).
+    pub fn is_rotation(self) -> bool {
+        matches!(self, x|x2|x3|y|y2|y3|z|z2|z3)
+    }
+
+    /// Check if the move is a slice move(M, E, S).
+    pub fn is_slice(self) -> bool {
+        matches!(self, M|M2|M3|E|E2|E3|S|S2|S3)
+    }
+
+    /// Check if the move is a wide move(Uw, Rw, Fw, Dw, Lw, Bw).
+    pub fn is_wide(self) -> bool {
+        matches!(self, Uw|Uw2|Uw3|Rw|Rw2|Rw3|Fw|Fw2|Fw3|Dw|Dw2|Dw3|Lw|Lw2|Lw3|Bw|Bw2|Bw3)
+    }
+
+    /// The axis of the move, named by its U, R or F face, `None` for N.
+    pub fn axis(self) -> Option<Color> {
+        match self {
+            U|U2|U3|D|D2|D3|E|E2|E3|Uw|Uw2|Uw3|Dw|Dw2|Dw3|y|y2|y3 => Some(Color::U),
+            R|R2|R3|L|L2|L3|M|M2|M3|Rw|Rw2|Rw3|Lw|Lw2|Lw3|x|x2|x3 => Some(Color::R),
+            F|F2|F3|B|B2|B3|S|S2|S3|Fw|Fw2|Fw3|Bw|Bw2|Bw3|z|z2|z3 => Some(Color::F),
+            N => None,
+        }
+    }
+
+    /// Number of moves in the `metric`, see `Metric`. An ATM count needs the neighbour moves, see `Formula::count`.
+    pub fn count(self, metric: Metric) -> usize {
+        if self == N || (self.is_rotation() && metric != Metric::ETM) {
+            return 0;
+        }
+        let quarters = if self.is_180() { 2 } else { 1 };
+        match metric {
+            Metric::HTM if self.is_slice() => 2,
+            Metric::QTM if self.is_slice() => 2 * quarters,
+            Metric::QTM => quarters,
+            Metric::HTM | Metric::STM | Metric::ETM | Metric::ATM => 1,
+        }
+    }
+
+    /// The axis of the move and the quarter turns of its three layers, clockwise seen from its U, R or F face,
+    /// eg. `R` -> (R, [1, 0, 0]), `M` -> (R, [0, 3, 0]), `Lw` -> (R, [0, 3, 3]), `x` -> (R, [1, 1, 1]).
+    fn layer_turns(self) -> Option<(Color, [usize; 3])> {
+        let n = if self.is_180() { 2 } else if self.is_counter_clockwise() { 3 } else { 1 };
+        let (axis, layers) = match self.get_face().as_str() {
+            "U" => (Color::U, [1, 0, 0]),
+            "E" => (Color::U, [0, 3, 0]),
+            "D" => (Color::U, [0, 0, 3]),
+            "Uw" => (Color::U, [1, 1, 0]),
+            "Dw" => (Color::U, [0, 3, 3]),
+            "y" => (Color::U, [1, 1, 1]),
+            "R" => (Color::R, [1, 0, 0]),
+            "M" => (Color::R, [0, 3, 0]),
+            "L" => (Color::R, [0, 0, 3]),
+            "Rw" => (Color::R, [1, 1, 0]),
+            "Lw" => (Color::R, [0, 3, 3]),
+            "x" => (Color::R, [1, 1, 1]),
+            "F" => (Color::F, [1, 0, 0]),
+            "S" => (Color::F, [0, 1, 0]),
+            "B" => (Color::F, [0, 0, 3]),
+            "Fw" => (Color::F, [1, 1, 0]),
+            "Bw" => (Color::F, [0, 3, 3]),
+            "z" => (Color::F, [1, 1, 1]),
+            _ => return None,
+        };
+        Some((axis, layers.map(|l: usize| l * n % 4)))
+    }
+
+    /// Relabel the move done at the orientation `center`(colors at the U, R, F, D, L, B positions)
+    /// to the move on the same layers done at the solved orientation, eg. `R` after `y` -> `B`.
+    fn relabel(self, center: &[Color; 6]) -> Move {
+        let (axis, [a, b, c]) = match self.layer_turns() {
+            Some(t) => t,
+            None => return N,
+        };
+        let face = center[axis as usize];
+        let (axis, turns) = match face as usize {
+            0..=2 => (face, [a, b, c]),
+            // The opposite face: the layers are reversed and the turns are counter-clockwise.
+            i => (ALL_COLORS[i - 3], [(4 - c) % 4, (4 - b) % 4, (4 - a) % 4]),
+        };
+        Move::from_layer_turns(axis, turns)
+    }
+
+    /// The move turning the layers of the `axis` by `turns`, N if there is no such move.
+    fn from_layer_turns(axis: Color, turns: [usize; 3]) -> Move {
+        ALL_MOVES_FULL
+            .into_iter()
+            .find(|m| m.layer_turns() == Some((axis, turns)))
+            .unwrap_or(N)
+    }
+
+    /// Mirror the move across the `plane`, eg. `R` -> `L'` across the M plane.
+    pub fn mirror(self, plane: Plane) -> Move {
+        let (axis, [a, b, c]) = match self.layer_turns() {
+            Some(t) => t,
+            None => return N,
+        };
+        let turns = match axis == plane.axis() {
+            // The layers are swapped, the turns keep their direction seen from the axis.
+            true => [c, b, a],
+            false => [(4 - a) % 4, (4 - b) % 4, (4 - c) % 4],
+        };
+        Move::from_layer_turns(axis, turns)
+    }
+
     pub fn get_face(&self) -> String {
         let ms = format!("{:?}", self);
         let ms = ms.as_bytes();
@@ -398,9 +669,84 @@ pub const S_MOVE: CubieCube = CubieCube {
     eo: [1, 0, 1, 0, 1, 0, 1, 0, 0, 0, 0, 0],
 };
 
+/// Mirror planes of the cube, named by the slice they contain:
+/// M swaps the R and L faces, E swaps U and D, S swaps F and B.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum Plane {
+    M,
+    E,
+    S,
+}
+
+impl Plane {
+    /// The axis perpendicular to the plane, named by its U, R or F face.
+    pub fn axis(self) -> Color {
+        match self {
+            Plane::M => Color::R,
+            Plane::E => Color::U,
+            Plane::S => Color::F,
+        }
+    }
+}
+
+/// Metrics of counting moves, following the WCA / speedsolving definitions.
+/// * HTM(Half Turn Metric): any turn of an outer layer (or a wide move) is 1 move, a slice move is 2, rotations are 0.
+/// * QTM(Quarter Turn Metric): a quarter turn is 1 move, a half turn is 2, a slice quarter turn is 2, rotations are 0.
+/// * STM(Slice Turn Metric): any turn of any layer (outer, slice or wide) is 1 move, rotations are 0.
+/// * ETM(Execution Turn Metric): every move is 1, rotations included.
+/// * ATM(Axial Turn Metric): consecutive turns about the same axis are 1 move, rotations are 0.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum Metric {
+    HTM,
+    QTM,
+    STM,
+    ETM,
+    ATM,
+}
+
+/// The tables of `Formula::axis_table` for each axis and metric.
+#[dynamic(lazy)]
+static AXIS_TABLES: HashMap<(Color, Metric), Vec<Vec<Move>>> = {
+    let metrics = [
+        Metric::HTM,
+        Metric::QTM,
+        Metric::STM,
+        Metric::ETM,
+        Metric::ATM,
+    ];
+    let mut tables = HashMap::new();
+    for axis in [Color::U, Color::R, Color::F] {
+        for metric in metrics {
+            tables.insert((axis, metric), Formula::axis_table(axis, metric));
+        }
+    }
+    tables
+};
+
+/// Options of `Formula::random_state_scramble_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrambleOptions {
+    /// Minimal length of the scramble, at least 2: a state solvable in fewer than 2 moves is rejected(WCA regulation 4b3).
+    pub min_length: usize,
+    /// Maximal length of the scramble, i.e, the max depth of the min2phase search.
+    pub max_length: usize,
+    /// Seed of the RNG for reproducible scrambles, a random seed if `None`.
+    pub seed: Option<u64>,
+}
+
+impl Default for ScrambleOptions {
+    fn default() -> Self {
+        Self {
+            min_length: 2,
+            max_length: 21,
+            seed: None,
+        }
+    }
+}
 
 /// `Formula` to is represent of `Move` sequence
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Formula {
     pub moves: Vec<Move>,
 }
@@ -411,6 +757,14 @@ impl Default for Formula {
     }
 }
 
+impl FromStr for Formula {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Formula::from_string(s)
+    }
+}
+
 impl Display for Formula {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s: String = self.moves.iter().map(|m|Move::to_string(m) + " ").collect();
@@ -418,20 +772,45 @@ impl Display for Formula {
     }
 }
 
+/// Serialize as the alg string, eg. "R U R' U'".
+#[cfg(feature = "serde")]
+impl serde::Serialize for Formula {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Formula {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Formula::from_string(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 impl Formula {
-    pub fn from_string(s: &str) -> Self {
-        let moves: Vec<Move> = s
-            .split_ascii_whitespace()
-            .map(|m| Move::from_str(m).unwrap())
-            .collect();
-        Self { moves }
+    /// Parse a formula in WCA notation, eg. "R U R' U'". Whitespaces are optional ("RUR'U'"),
+    /// lowercase wide moves ("r"), "R2'", "R3", repeated groups ("(R U R' U')3", at most 100 times) and "//" comments are accepted.
+    /// Groups are nested at most 32 deep and expand to at most 10000 moves.
+    pub fn from_string(s: &str) -> Result<Self, Error> {
+        let mut parser = Parser::new(s);
+        let moves = parser.sequence()?;
+        if parser.peek().is_some() {
+            return Err(parser.error(parser.pos));
+        }
+        Ok(Self { moves })
     }
     /// Generate a random scramble formula.
     pub fn scramble() -> Self {
+        Self::scramble_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a random scramble formula with the `rng`, eg. `Generator::seeded_rng(seed)` for a reproducible scramble.
+    pub fn scramble_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
         let mut moves = Vec::new();
         let mut p = B;
         for _ in 0..25 {
-            let m = match random::<u32>() % 6 {
+            let m = match rng.gen_range(0..6u32) {
                 0 => U,
                 1 => R,
                 2 => F,
@@ -442,7 +821,7 @@ impl Formula {
             if m == p {
                 continue;
             }
-            let s = match random::<u32>() % 3 {
+            let s = match rng.gen_range(0..3u32) {
                 0 => "",
                 1 => "2",
                 _ => "'",
@@ -455,27 +834,300 @@ impl Formula {
         Self { moves }
     }
 
+    /// Generate a random state scramble: a uniformly random state solved by min2phase, the scramble is the inverse solution.
+    /// # Example
+    /// ```rust
+    /// use rcuber::cubie::CubieCube;
+    /// use rcuber::moves::{Formula, ScrambleOptions};
+    ///
+    /// fn main() {
+    ///     let scramble = Formula::random_state_scramble();
+    ///     assert!(scramble.moves.len() >= 2);
+    ///     let options = ScrambleOptions { seed: Some(2024), ..Default::default() };
+    ///     let scramble = Formula::random_state_scramble_with(&options).unwrap();
+    ///     assert_eq!(scramble, Formula::random_state_scramble_with(&options).unwrap());
+    ///     assert_ne!(CubieCube::default().apply_formula(&scramble), CubieCube::default());
+    /// }
+    /// ```
+    pub fn random_state_scramble() -> Self {
+        Self::random_state_scramble_with(&ScrambleOptions::default()).unwrap()
+    }
+
+    /// Generate a random state scramble with the length bounds and seed of the `options`.
+    /// Fails with `NoSolutionForMaxDepth` if no scramble is found, eg. `max_length` is too small.
+    pub fn random_state_scramble_with(options: &ScrambleOptions) -> Result<Self, Error> {
+        with_options_rng(options, |rng| random_state_scramble(options, rng))
+    }
+
+    /// Generate a 3BLD scramble: a random state scramble followed by a random orientation in wide moves(WCA),
+    /// eg. "... Rw' Uw2".
+    /// # Example
+    /// ```rust
+    /// use rcuber::moves::{Formula, ScrambleOptions};
+    ///
+    /// fn main() {
+    ///     let scramble = Formula::bld_scramble();
+    ///     assert!(scramble.moves.len() >= 2);
+    ///     let options = ScrambleOptions { seed: Some(3), ..Default::default() };
+    ///     assert_eq!(Formula::bld_scramble_with(&options).unwrap(), Formula::bld_scramble_with(&options).unwrap());
+    /// }
+    /// ```
+    pub fn bld_scramble() -> Self {
+        Self::bld_scramble_with(&ScrambleOptions::default()).unwrap()
+    }
+
+    /// Generate a 3BLD scramble with the length bounds(of the random state scramble) and seed of the `options`.
+    pub fn bld_scramble_with(options: &ScrambleOptions) -> Result<Self, Error> {
+        with_options_rng(options, |rng| {
+            let mut scramble = random_state_scramble(options, rng)?;
+            // The 24 orientations: a face brought to U, then a turn about U.
+            let up = [N, Rw, Rw2, Rw3, Fw, Fw3][rng.gen_range(0..6u32) as usize];
+            let turn = [N, Uw, Uw2, Uw3][rng.gen_range(0..4u32) as usize];
+            scramble
+                .moves
+                .extend([up, turn].iter().filter(|m| **m != N));
+            Ok(scramble)
+        })
+    }
+
+    /// Generate a FMC scramble: a random state scramble wrapped in "R' U' F ... R' U' F"(WCA),
+    /// the inner moves don't start on the F axis nor end on the R axis, so nothing cancels.
+    /// # Example
+    /// ```rust
+    /// use rcuber::moves::Formula;
+    ///
+    /// fn main() {
+    ///     let scramble = Formula::fmc_scramble().to_string();
+    ///     assert!(scramble.starts_with("R' U' F ") && scramble.ends_with(" R' U' F"));
+    /// }
+    /// ```
+    pub fn fmc_scramble() -> Self {
+        Self::fmc_scramble_with(&ScrambleOptions::default()).unwrap()
+    }
+
+    /// Generate a FMC scramble with the length bounds(of the inner moves) and seed of the `options`.
+    pub fn fmc_scramble_with(options: &ScrambleOptions) -> Result<Self, Error> {
+        with_options_rng(options, |rng| state_scramble(options, rng, &[R3, U3, F]))
+    }
+
+    /// Cancel the moves, minimal in HTM, see `optimise_with`.
     pub fn optimise(&self) -> Self {
+        self.optimise_with(Metric::HTM)
+    }
+
+    /// Cancel the moves, i.e, an equivalent formula(cube rotations included) of minimal length in the `metric`.
+    /// Consecutive moves on the same axis commute, so they are merged into turns of the three layers of the axis,
+    /// then rewritten with the fewest moves among face, slice, wide moves and rotations of the axis.
+    /// eg. `U D U'` -> `D`, `R L R` -> `R2 L`, `R L' x'` -> `M`, `Rw R'` -> `M'`.
+    /// The moves are merged as well once the rotations are moved to the end(see `without_rotations`),
+    /// so moves separated by rotations cancel too, eg. `y R y' B'` -> ``; the shorter of both is kept.
+    /// # Example
+    /// ```rust
+    /// use rcuber::moves::{Formula, Metric};
+    ///
+    /// fn main() {
+    ///     let f = Formula::from_string("F U D U' R L R B2 Fw' S").unwrap();
+    ///     assert_eq!(f.optimise_with(Metric::HTM).to_string(), "F D R2 L F' B2");
+    /// }
+    /// ```
+    pub fn optimise_with(&self, metric: Metric) -> Self {
+        let merged = self.merge(metric);
+        let unrotated = self.without_rotations().merge(metric);
+        let key = |f: &Formula| (f.count(metric), f.moves.len());
+        match key(&unrotated) < key(&merged) {
+            true => unrotated,
+            false => merged,
+        }
+    }
+
+    /// Merge the consecutive moves on the same axis and rewrite them with the fewest moves in the `metric`.
+    fn merge(&self, metric: Metric) -> Self {
+        let mut groups: Vec<(Color, [usize; 3])> = Vec::new();
+        for m in &self.moves {
+            let (axis, turns) = match m.layer_turns() {
+                Some(t) => t,
+                None => continue,
+            };
+            match groups.last_mut() {
+                Some((a, t)) if *a == axis => {
+                    for k in 0..3 {
+                        t[k] = (t[k] + turns[k]) % 4;
+                    }
+                    if *t == [0, 0, 0] {
+                        groups.pop();
+                    }
+                }
+                _ => groups.push((axis, turns)),
+            }
+        }
         let mut moves = Vec::new();
-        for m in self.moves.clone() {
-            let p = *moves.last().unwrap_or(&N);
-            if m == N {
-                continue;
+        for (axis, turns) in groups {
+            let table = &AXIS_TABLES[&(axis, metric)];
+            moves.extend(&table[turns[0] * 16 + turns[1] * 4 + turns[2]]);
+        }
+        Self { moves }
+    }
+
+    /// The shortest formulas in the `metric` of the 64 layer turns of the `axis`, indexed by `a * 16 + b * 4 + c`.
+    fn axis_table(axis: Color, metric: Metric) -> Vec<Vec<Move>> {
+        let bases = match axis {
+            Color::U => [U, D, E, Uw, Dw, y],
+            Color::R => [R, L, M, Rw, Lw, x],
+            _ => [F, B, S, Fw, Bw, z],
+        };
+        let candidates: Vec<Move> = bases.iter().flat_map(|m| [*m, *m * 2, *m * 3]).collect();
+        let mut keys = [(usize::MAX, 0, 0); 64];
+        let mut table = vec![Vec::new(); 64];
+        let mut add = |moves: Vec<Move>| {
+            let mut t = [0; 3];
+            for m in &moves {
+                let (_, turns) = m.layer_turns().unwrap();
+                for k in 0..3 {
+                    t[k] = (t[k] + turns[k]) % 4;
+                }
+            }
+            let f = Formula { moves };
+            // Shortest in the metric, then with the fewest moves, then with the fewest slice, wide moves and rotations.
+            let special = f
+                .moves
+                .iter()
+                .filter(|m| m.is_slice() || m.is_wide() || m.is_rotation())
+                .count();
+            let key = (f.count(metric), f.moves.len(), special);
+            let i = t[0] * 16 + t[1] * 4 + t[2];
+            if key < keys[i] {
+                keys[i] = key;
+                table[i] = f.moves;
             }
-            if m.get_face() == p.get_face() {
-                let _m = m + p;
-                moves.pop();
-                if _m == N {
-                    continue;
+        };
+        add(Vec::new());
+        for i in 0..18 {
+            add(vec![candidates[i]]);
+            for j in (i / 3 + 1) * 3..18 {
+                add(vec![candidates[i], candidates[j]]);
+                for k in (j / 3 + 1) * 3..18 {
+                    add(vec![candidates[i], candidates[j], candidates[k]]);
                 }
-                moves.push(_m);
-            } else {
-                moves.push(m);
             }
         }
+        table
+    }
+
+    /// Length of the formula in the `metric`.
+    /// # Example
+    /// ```rust
+    /// use rcuber::moves::{Formula, Metric};
+    ///
+    /// fn main() {
+    ///     let f = Formula::from_string("R U2 M' x R L'").unwrap();
+    ///     assert_eq!(f.count(Metric::HTM), 6);
+    ///     assert_eq!(f.count(Metric::QTM), 7);
+    ///     assert_eq!(f.count(Metric::STM), 5);
+    ///     assert_eq!(f.count(Metric::ETM), 6);
+    ///     assert_eq!(f.count(Metric::ATM), 4);
+    /// }
+    /// ```
+    pub fn count(&self, metric: Metric) -> usize {
+        if metric != Metric::ATM {
+            return self.moves.iter().map(|m| m.count(metric)).sum();
+        }
+        let mut count = 0;
+        let mut axis = None;
+        for m in &self.moves {
+            if m.count(metric) == 0 {
+                // A rotation ends the current group of axial turns.
+                axis = None;
+                continue;
+            }
+            if m.axis() != axis {
+                count += 1;
+                axis = m.axis();
+            }
+        }
+        count
+    }
+
+    /// Remove the cube rotations by relabelling the moves following them, wide and slice moves are turned into
+    /// face moves as well. The net orientation is kept by at most two rotations at the end.
+    /// # Example
+    /// ```rust
+    /// use rcuber::moves::Formula;
+    ///
+    /// fn main() {
+    ///     let f = Formula::from_string("y R U R' Rw").unwrap();
+    ///     assert_eq!(f.without_rotations().to_string(), "B U F B' x z'");
+    /// }
+    /// ```
+    pub fn without_rotations(&self) -> Self {
+        let mut orientation = SOLVED_CUBIE_CUBE;
+        let mut faces = Vec::new();
+        for m in &self.moves {
+            let (axis, [a, b, c]) = match m.relabel(&orientation.center).layer_turns() {
+                Some(t) => t,
+                None => continue,
+            };
+            let [face, opposite] = match axis {
+                Color::U => [U, D],
+                Color::R => [R, L],
+                _ => [F, B],
+            };
+            faces.push(face * ((a + 4 - b) % 4));
+            faces.push(opposite * ((b + 4 - c) % 4));
+            // The middle layer turns with the whole cube.
+            let (axis, [_, b, _]) = m.layer_turns().unwrap();
+            let rotation = match axis {
+                Color::U => y,
+                Color::R => x,
+                _ => z,
+            };
+            orientation = orientation.apply_move(rotation * b);
+        }
+        let mut moves = Formula { moves: faces }.merge(Metric::HTM).moves;
+        let rotations = [x, x2, x3, y, y2, y3, z, z2, z3];
+        let net = std::iter::once(Vec::new())
+            .chain(rotations.iter().map(|r| vec![*r]))
+            .chain(
+                rotations
+                    .iter()
+                    .flat_map(|r| rotations.iter().map(move |s| vec![*r, *s])),
+            )
+            .find(|r| SOLVED_CUBIE_CUBE.apply_moves(r).center == orientation.center)
+            .unwrap();
+        moves.extend(net);
         Self { moves }
     }
 
+    /// Conjugate the formula by the cube `rotation`, i.e, the formula done after the `rotation` and
+    /// relabelled to the solved orientation, eg. `R U R'` by `y` -> `B U B'`. Non-rotation moves don't change the formula.
+    pub fn rotated_by(&self, rotation: Move) -> Self {
+        if !rotation.is_rotation() {
+            return self.clone();
+        }
+        let center = SOLVED_CUBIE_CUBE.apply_move(rotation).center;
+        Self {
+            moves: self.moves.iter().map(|m| m.relabel(&center)).collect(),
+        }
+    }
+
+    /// Mirror the formula across the `plane`, eg. `R U R'` -> `L' U' L` across the M plane.
+    /// # Example
+    /// ```rust
+    /// use rcuber::cubie::CubieCube;
+    /// use rcuber::moves::{Formula, Plane};
+    ///
+    /// fn main() {
+    ///     let f = Formula::from_string("R U R' Rw x").unwrap();
+    ///     assert_eq!(f.mirror(Plane::M).to_string(), "L' U' L Lw' x");
+    ///     let cc = CubieCube::default();
+    ///     assert_eq!(cc.apply_formula(&f.mirror(Plane::M)), cc.apply_formula(&f).mirror(Plane::M));
+    /// }
+    /// ```
+    pub fn mirror(&self, plane: Plane) -> Self {
+        Self {
+            moves: self.moves.iter().map(|m| m.mirror(plane)).collect(),
+        }
+    }
+
     pub fn inverse(&self) -> Self {
         let mut moves = Vec::new();
         for m in self.moves.clone() {
@@ -486,10 +1138,107 @@ impl Formula {
     }
 }
 
+/// Run `f` with the rng of the seed of the `options`, or a random one.
+fn with_options_rng<T>(options: &ScrambleOptions, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    match options.seed {
+        Some(seed) => f(&mut Generator::seeded_rng(seed)),
+        None => f(&mut rand::thread_rng()),
+    }
+}
+
+/// Draw random states until one has a min2phase solution within the length bounds.
+fn random_state_scramble<R: Rng + ?Sized>(
+    options: &ScrambleOptions,
+    rng: &mut R,
+) -> Result<Formula, Error> {
+    state_scramble(options, rng, &[])
+}
+
+/// Scramble a random state by `wrap` + inner moves + `wrap`, the inner moves are the inverse of a min2phase solution.
+/// The inner moves don't start on the axis of the last move of `wrap`, nor end on the axis of its first move,
+/// else the state is kept and a random move is added on the offending side, so the states stay uniform.
+fn state_scramble<R: Rng + ?Sized>(
+    options: &ScrambleOptions,
+    rng: &mut R,
+    wrap: &[Move],
+) -> Result<Formula, Error> {
+    let min_length = options.min_length.max(2);
+    if min_length > options.max_length {
+        return Err(Error::NoSolutionForMaxDepth);
+    }
+    let first_axis = wrap.first().and_then(|m| m.axis());
+    let last_axis = wrap.last().and_then(|m| m.axis());
+    let inverse = SOLVED_CUBIE_CUBE.apply_moves(wrap).inverse_cubie_cube();
+    let mut solver = Solver::default();
+    for _ in 0..100 {
+        let cc = Generator::random_with_rng(rng);
+        // A state solvable in fewer than 2 moves.
+        if cc == SOLVED_CUBIE_CUBE
+            || ALL_MOVES
+                .iter()
+                .any(|m| SOLVED_CUBIE_CUBE.apply_move(*m) == cc)
+        {
+            continue;
+        }
+        // wrap * inner * wrap = cc
+        let target = inverse * cc * inverse;
+        let (mut before, mut after): (Option<Move>, Option<Move>) = (None, None);
+        for _ in 0..20 {
+            let pads = before.iter().chain(after.iter()).count();
+            let mut state = SOLVED_CUBIE_CUBE;
+            if let Some(m) = before {
+                state = state.apply_move(m.get_inverse());
+            }
+            state = state * target;
+            if let Some(m) = after {
+                state = state.apply_move(m.get_inverse());
+            }
+            let facelet = FaceCube::try_from(&state)?.to_string();
+            let Ok(solution) = solver.solve(&facelet, options.max_length - pads, 1000000, 0, 0)
+            else {
+                break;
+            };
+            let mut moves: Vec<Move> = before.into_iter().collect();
+            moves.extend(solution.optimise().inverse().moves);
+            moves.extend(after);
+            let inner = Formula { moves }.optimise();
+            let starts_ok =
+                last_axis.is_none() || inner.moves.first().and_then(|m| m.axis()) != last_axis;
+            let ends_ok =
+                first_axis.is_none() || inner.moves.last().and_then(|m| m.axis()) != first_axis;
+            if starts_ok && ends_ok {
+                if inner.moves.len() < min_length {
+                    break;
+                }
+                let mut moves = wrap.to_vec();
+                moves.extend(inner.moves);
+                moves.extend(wrap);
+                return Ok(Formula { moves });
+            }
+            if !starts_ok {
+                before = Some(random_move_off_axis(rng, last_axis));
+            }
+            if !ends_ok {
+                after = Some(random_move_off_axis(rng, first_axis));
+            }
+        }
+    }
+    Err(Error::NoSolutionForMaxDepth)
+}
+
+/// A random face move not on the `axis`.
+fn random_move_off_axis<R: Rng + ?Sized>(rng: &mut R, axis: Option<Color>) -> Move {
+    let moves: Vec<Move> = ALL_MOVES.into_iter().filter(|m| m.axis() != axis).collect();
+    moves[rng.gen_range(0..moves.len() as u32) as usize]
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Formula;
     use super::Move::*;
+    use super::{Formula, Metric, Move, ScrambleOptions};
+    use crate::constants::ALL_MOVES_FULL;
+    use crate::cubie::CubieCube;
+    use crate::error::Error;
 
     #[test]
     fn test_move_add_mul() {
@@ -520,6 +1269,108 @@ mod tests {
         };
         let moves = f.optimise();
         println!("{:?}", moves);
+        assert_eq!(moves.moves, vec![R2, U, R3, U3, R2, U2, R3]);
+
+        let optimise = |s: &str, metric| {
+            Formula::from_string(s)
+                .unwrap()
+                .optimise_with(metric)
+                .to_string()
+        };
+        assert_eq!(optimise("U D U'", Metric::HTM), "D");
+        assert_eq!(optimise("R L R", Metric::HTM), "R2 L");
+        assert_eq!(optimise("R U U' R'", Metric::HTM), "");
+        assert_eq!(optimise("R L' x'", Metric::HTM), "M");
+        assert_eq!(optimise("Rw R'", Metric::STM), "M'");
+        assert_eq!(optimise("R L'", Metric::HTM), "R L'");
+        assert_eq!(optimise("R L'", Metric::STM), "M x");
+        assert_eq!(optimise("x R'", Metric::HTM), "Lw'");
+        assert_eq!(optimise("y y'", Metric::ETM), "");
+        assert_eq!(optimise("y R y' B'", Metric::HTM), "");
+        assert_eq!(optimise("y R y' B", Metric::QTM), "B2");
+        assert_eq!(optimise("x U x' F'", Metric::STM), "");
+    }
+
+    #[test]
+    fn test_optimise_random() {
+        let all: Vec<Move> = (0..54)
+            .map(|i| {
+                [U, R, F, D, L, B, M, E, S, Uw, Rw, Fw, Dw, Lw, Bw, x, y, z][i / 3] * (i % 3 + 1)
+            })
+            .collect();
+        for _ in 0..200 {
+            let moves: Vec<Move> = (0..20)
+                .map(|_| all[rand::random::<usize>() % all.len()])
+                .collect();
+            let f = Formula { moves };
+            let cc = CubieCube::default().apply_formula(&f);
+            for metric in [
+                Metric::HTM,
+                Metric::QTM,
+                Metric::STM,
+                Metric::ETM,
+                Metric::ATM,
+            ] {
+                let o = f.optimise_with(metric);
+                assert_eq!(
+                    CubieCube::default().apply_formula(&o),
+                    cc,
+                    "{} {:?}",
+                    f,
+                    metric
+                );
+                assert!(o.count(metric) <= f.count(metric), "{} {:?}", f, metric);
+                assert_eq!(o.optimise_with(metric).moves, o.moves);
+            }
+        }
+    }
+
+    #[test]
+    fn test_without_rotations() {
+        let all: Vec<Move> = ALL_MOVES_FULL.to_vec();
+        for _ in 0..200 {
+            let moves: Vec<Move> = (0..20)
+                .map(|_| all[rand::random::<usize>() % all.len()])
+                .collect();
+            let f = Formula { moves };
+            let w = f.without_rotations();
+            assert_eq!(
+                CubieCube::default().apply_formula(&w),
+                CubieCube::default().apply_formula(&f),
+                "{}",
+                f
+            );
+            let faces = w.moves.iter().take_while(|m| !m.is_rotation()).count();
+            assert!(w.moves.len() - faces <= 2);
+            assert!(w.moves[faces..].iter().all(|m| m.is_rotation()), "{}", w);
+            assert!(
+                w.moves[..faces]
+                    .iter()
+                    .all(|m| !m.is_slice() && !m.is_wide()),
+                "{}",
+                w
+            );
+        }
+        let f = Formula::from_string("M2 U M U2 M' U M2").unwrap();
+        assert_eq!(
+            f.without_rotations().to_string(),
+            "R2 L2 D R L' F2 R' L D R2 L2"
+        );
+    }
+
+    #[test]
+    fn test_rotated_by() {
+        let f = Formula::from_string("R U R' Rw M").unwrap();
+        assert_eq!(f.rotated_by(y).to_string(), "B U B' Bw S");
+        assert_eq!(f.rotated_by(x2).to_string(), "R D R' Rw M");
+        assert_eq!(f.rotated_by(R), f);
+        for r in [x, y, z, x3, y2, z3] {
+            let cc = CubieCube::default()
+                .apply_move(r)
+                .apply_formula(&f)
+                .apply_move(r.get_inverse());
+            assert_eq!(CubieCube::default().apply_formula(&f.rotated_by(r)), cc);
+        }
     }
 
     #[test]
@@ -527,4 +1378,113 @@ mod tests {
         let r = Formula::scramble();
         println!("{:?}", r);
     }
+
+    #[test]
+    fn test_random_state_scramble() {
+        let scramble = Formula::random_state_scramble();
+        assert!(scramble.moves.len() >= 2 && scramble.moves.len() <= 21);
+        assert!(scramble
+            .moves
+            .iter()
+            .all(|m| !m.is_rotation() && !m.is_slice() && !m.is_wide()));
+        let options = ScrambleOptions {
+            min_length: 15,
+            max_length: 20,
+            seed: Some(7),
+        };
+        let scramble = Formula::random_state_scramble_with(&options).unwrap();
+        assert!(scramble.moves.len() >= 15 && scramble.moves.len() <= 20);
+        assert_eq!(
+            Formula::random_state_scramble_with(&options).unwrap(),
+            scramble
+        );
+        let other = ScrambleOptions {
+            seed: Some(8),
+            ..options.clone()
+        };
+        assert_ne!(
+            Formula::random_state_scramble_with(&other).unwrap(),
+            scramble
+        );
+        let options = ScrambleOptions {
+            min_length: 10,
+            max_length: 5,
+            seed: None,
+        };
+        assert!(Formula::random_state_scramble_with(&options).is_err());
+    }
+
+    #[test]
+    fn test_count() {
+        let count = |s: &str, metric| Formula::from_string(s).unwrap().count(metric);
+        assert_eq!(count("R U R' U'", Metric::HTM), 4);
+        assert_eq!(count("R2 U2 M2 E'", Metric::QTM), 10);
+        assert_eq!(count("Rw2 M x2 y", Metric::HTM), 3);
+        assert_eq!(count("Rw2 M x2 y", Metric::STM), 2);
+        assert_eq!(count("Rw2 M x2 y", Metric::ETM), 4);
+        assert_eq!(count("R L' M2 U D E F x F", Metric::ATM), 4);
+        assert_eq!(Formula::default().count(Metric::ATM), 0);
+        assert_eq!(N.count(Metric::ETM), 0);
+    }
+
+    #[test]
+    fn test_from_string() {
+        let f = Formula::from_string("R U R' U'").unwrap();
+        assert_eq!(f.moves, vec![R, U, R3, U3]);
+        assert_eq!(Formula::from_string("RUR'U'").unwrap().moves, f.moves);
+        assert_eq!(
+            Formula::from_string("(R U R' U')3").unwrap().moves.len(),
+            12
+        );
+        assert_eq!(
+            Formula::from_string("r u' Rw2 x R2' R3 R4 M' y2")
+                .unwrap()
+                .moves,
+            vec![Rw, Uw3, Rw2, x, R2, R3, M3, y2]
+        );
+        let f = Formula::from_string("R U // sexy\n(R' U')2 // twice\n").unwrap();
+        assert_eq!(f.moves, vec![R, U, R3, U3, R3, U3]);
+        assert!(Formula::from_string("").unwrap().moves.is_empty());
+        assert_eq!("F2".parse::<Move>().unwrap(), F2);
+        assert_eq!("l'".parse::<Move>().unwrap(), Lw3);
+    }
+
+    #[test]
+    fn test_from_string_error() {
+        let err = |s: &str| match Formula::from_string(s) {
+            Err(Error::InvalidScramble { position, token }) => (position, token),
+            r => panic!("Unexpected result: {:?}", r),
+        };
+        assert_eq!(err("R U X2 F"), (4, "X2".to_string()));
+        assert_eq!(err("R (U F"), (2, "(U".to_string()));
+        assert_eq!(err("R U) F"), (3, ")".to_string()));
+        assert_eq!(err("R Mw"), (3, "w".to_string()));
+        assert_eq!(err("(R)999999999999 U"), (3, "999999999999".to_string()));
+        assert_eq!(err("(R U)101"), (5, "101".to_string()));
+        assert_eq!(Formula::from_string("(R U)100").unwrap().moves.len(), 200);
+        assert_eq!(err("((((R)100)100)100)100").0, 1);
+        assert_eq!(
+            Formula::from_string("((R)100)100").unwrap().moves.len(),
+            10_000
+        );
+        assert_eq!(err(&"(".repeat(200_000)).0, 32);
+        assert!(Formula::from_string(&format!("{}R{}", "(".repeat(32), ")".repeat(32))).is_ok());
+        assert!("R U".parse::<Move>().is_err());
+        assert!("".parse::<Move>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        for m in ALL_MOVES_FULL.iter() {
+            let s = serde_json::to_string(m).unwrap();
+            assert_eq!(s, format!("\"{}\"", m));
+            assert_eq!(serde_json::from_str::<Move>(&s).unwrap(), *m);
+        }
+        let f = Formula::from_string("R U R' U' Rw2 M' y").unwrap();
+        let s = serde_json::to_string(&f).unwrap();
+        assert_eq!(s, "\"R U R' U' Rw2 M' y\"");
+        assert_eq!(serde_json::from_str::<Formula>(&s).unwrap(), f);
+        assert!(serde_json::from_str::<Formula>("\"R X\"").is_err());
+    }
 }
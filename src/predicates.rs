@@ -0,0 +1,243 @@
+//! # Predicates
+//! Check if a step is solved in any of the 24 orientations of the cube, i.e, with any colour on the bottom.
+//! A step is defined for the standard orientation(bottom D, front F) and checked on the cube seen from each orientation:
+//! * Cross: the 4 edges of the bottom layer.
+//! * F2L pair: the front right corner and edge of the first two layers.
+//! * 2x2x2 block: the down back left corner and its 3 edges.
+//! * 2x2x3 block: the down left 2x2x3 block, i.e, the 2x2x2 block extended to the front.
+//! * Roux block: the 1x2x3 block of the left side (Roux first block), the M slice needn't be aligned.
+//! * EO: the edges are oriented for the front/back axis, i.e, they can be solved without F and B quarter turns.
+//! * EOLine: EO and the down front and down back edges (ZZ).
+//!
+//! The cube is first rotated so that its centers are at the solved positions.
+//! # Example
+//! ```rust
+//! use rcuber::cubie::CubieCube;
+//! use rcuber::facelet::Color;
+//! use rcuber::moves::Formula;
+//! use rcuber::predicates::{self, Step};
+//!
+//! fn main() {
+//!     let cc = CubieCube::default().apply_formula(&Formula::from_string("R").unwrap());
+//!     assert!(predicates::find(&cc, Step::Cross, Some(Color::D)).is_none());
+//!     let orientation = predicates::find(&cc, Step::Cross, None).unwrap();
+//!     assert_eq!(orientation.down, Color::L);
+//!     assert!(predicates::is_solved(&cc, Step::Block2x2x3, &orientation.rotation));
+//! }
+//! ```
+
+use crate::cubie::{Corner, CubieCube, Edge, SOLVED_CUBIE_CUBE};
+use crate::facelet::Color;
+use crate::moves::Move::{self, *};
+use crate::symmetry::Symmetry;
+
+/// Steps of the solving methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    Cross,
+    F2LPair,
+    Block2x2x2,
+    Block2x2x3,
+    RouxBlock,
+    EO,
+    EOLine,
+}
+
+/// An orientation of the cube, i.e, the rotation bringing the `down` colour to the bottom and the `front` colour to the front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orientation {
+    pub rotation: Vec<Move>,
+    pub down: Color,
+    pub front: Color,
+}
+
+impl Step {
+    /// The corners and edges solved by the step, in the standard orientation.
+    fn pieces(self) -> (&'static [Corner], &'static [Edge]) {
+        match self {
+            Step::Cross => (&[], &[Edge::DR, Edge::DF, Edge::DL, Edge::DB]),
+            Step::F2LPair => (&[Corner::DFR], &[Edge::FR]),
+            Step::Block2x2x2 => (&[Corner::DBL], &[Edge::DL, Edge::DB, Edge::BL]),
+            Step::Block2x2x3 => (
+                &[Corner::DLF, Corner::DBL],
+                &[Edge::DL, Edge::DF, Edge::DB, Edge::FL, Edge::BL],
+            ),
+            Step::RouxBlock => (&[Corner::DLF, Corner::DBL], &[Edge::DL, Edge::FL, Edge::BL]),
+            Step::EO => (&[], &[]),
+            Step::EOLine => (&[], &[Edge::DF, Edge::DB]),
+        }
+    }
+
+    /// Check if the step is solved in the standard orientation.
+    fn is_solved(self, cube: &CubieCube) -> bool {
+        if self == Step::RouxBlock {
+            // The block is in the L layer, an unaligned M slice is an L turn of the block.
+            return [N, L, L2, L3]
+                .iter()
+                .any(|m| self.pieces_solved(&cube.apply_move(*m)));
+        }
+        self.pieces_solved(cube)
+    }
+
+    fn pieces_solved(self, cube: &CubieCube) -> bool {
+        let (corners, edges) = self.pieces();
+        let eo = match self {
+            Step::EO | Step::EOLine => cube.eo.iter().all(|o| *o == 0),
+            _ => true,
+        };
+        eo && corners
+            .iter()
+            .all(|c| cube.cp[*c as usize] == *c && cube.co[*c as usize] == 0)
+            && edges
+                .iter()
+                .all(|e| cube.ep[*e as usize] == *e && cube.eo[*e as usize] == 0)
+    }
+}
+
+/// The 24 orientations, by the rotation symmetries.
+pub(crate) fn orientations() -> Vec<(Symmetry, Orientation)> {
+    Symmetry::all()
+        .into_iter()
+        .filter(|s| !s.is_mirror())
+        .map(|s| {
+            // Conjugating by the symmetry is the cube seen after the inverse rotation.
+            let rotation = s.inverse().rotation();
+            let center = SOLVED_CUBIE_CUBE.apply_moves(&rotation).center;
+            let orientation = Orientation {
+                rotation,
+                down: center[Color::D as usize],
+                front: center[Color::F as usize],
+            };
+            (s, orientation)
+        })
+        .collect()
+}
+
+/// Rotate the cube so that its centers are at the solved positions.
+fn normalise(cube: &CubieCube) -> CubieCube {
+    if cube.center == SOLVED_CUBIE_CUBE.center {
+        return *cube;
+    }
+    orientations()
+        .into_iter()
+        .map(|(_, o)| cube.apply_moves(&o.rotation))
+        .find(|cc| cc.center == SOLVED_CUBIE_CUBE.center)
+        .unwrap_or(*cube)
+}
+
+/// Check if the step is solved in the orientation given by the `rotation`, eg. `&[x2]` for the cross on U.
+pub fn is_solved(cube: &CubieCube, step: Step, rotation: &[Move]) -> bool {
+    let center = SOLVED_CUBIE_CUBE.apply_moves(rotation).center;
+    let cube = normalise(cube);
+    orientations()
+        .into_iter()
+        .find(|(_, o)| SOLVED_CUBIE_CUBE.apply_moves(&o.rotation).center == center)
+        .is_some_and(|(s, _)| step.is_solved(&cube.conjugate(s)))
+}
+
+/// All the orientations in which the step is solved.
+pub fn solved_orientations(cube: &CubieCube, step: Step) -> Vec<Orientation> {
+    let cube = normalise(cube);
+    orientations()
+        .into_iter()
+        .filter(|(s, _)| step.is_solved(&cube.conjugate(*s)))
+        .map(|(_, o)| o)
+        .collect()
+}
+
+/// Find an orientation in which the step is solved, with the `down` colour on the bottom if given.
+pub fn find(cube: &CubieCube, step: Step, down: Option<Color>) -> Option<Orientation> {
+    solved_orientations(cube, step)
+        .into_iter()
+        .find(|o| match down {
+            Some(d) => o.down == d,
+            None => true,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moves::Formula;
+
+    fn cube(s: &str) -> CubieCube {
+        CubieCube::default().apply_formula(&Formula::from_string(s).unwrap())
+    }
+
+    #[test]
+    fn test_solved() {
+        let steps = [
+            Step::Cross,
+            Step::F2LPair,
+            Step::Block2x2x2,
+            Step::Block2x2x3,
+            Step::RouxBlock,
+            Step::EO,
+            Step::EOLine,
+        ];
+        for cc in [CubieCube::default(), cube("y"), cube("x z'")] {
+            for step in steps {
+                assert_eq!(solved_orientations(&cc, step).len(), 24);
+            }
+        }
+        let sune = cube("R U R' U R U2 R'");
+        let downs: Vec<Color> = solved_orientations(&sune, Step::Cross)
+            .iter()
+            .map(|o| o.down)
+            .collect();
+        assert_eq!(
+            downs,
+            [
+                Color::D,
+                Color::D,
+                Color::D,
+                Color::D,
+                Color::F,
+                Color::F,
+                Color::F,
+                Color::F
+            ]
+        );
+        assert!(is_solved(&sune, Step::Block2x2x3, &[]));
+        assert!(is_solved(&sune, Step::F2LPair, &[y2]));
+        assert!(!is_solved(&sune, Step::Cross, &[x2]));
+    }
+
+    #[test]
+    fn test_colour_neutral() {
+        let cc = cube("R U R' F' L2");
+        assert!(solved_orientations(&cc, Step::Cross).is_empty());
+        let cc = cube("F");
+        let o = find(&cc, Step::Cross, None).unwrap();
+        assert_eq!(o.down, Color::B);
+        assert!(is_solved(&cc, Step::Cross, &o.rotation));
+        assert!(is_solved(&cc, Step::Cross, &[x]));
+        assert!(!is_solved(&cc, Step::Cross, &[x3]));
+        let pairs = solved_orientations(&cube("R U R'"), Step::F2LPair);
+        let d_fronts: Vec<Color> = pairs
+            .iter()
+            .filter(|o| o.down == Color::D)
+            .map(|o| o.front)
+            .collect();
+        assert_eq!(d_fronts, [Color::L, Color::B, Color::R]);
+        let cc = cube("M2 U2");
+        assert!(find(&cc, Step::RouxBlock, Some(Color::D)).is_some());
+        assert!(find(&cc, Step::Block2x2x3, Some(Color::D)).is_none());
+    }
+
+    #[test]
+    fn test_eo() {
+        let cc = cube("R U L D2 B2");
+        assert!(is_solved(&cc, Step::EO, &[]));
+        assert!(!is_solved(&cc, Step::EOLine, &[]));
+        let cc = cube("F");
+        assert!(!is_solved(&cc, Step::EO, &[]));
+        let fronts: Vec<Color> = solved_orientations(&cc, Step::EO)
+            .iter()
+            .map(|o| o.front)
+            .collect();
+        assert_eq!(fronts.len(), 16);
+        assert!(fronts.iter().all(|f| *f != Color::F && *f != Color::B));
+        assert!(is_solved(&cube("R U L' U2"), Step::EOLine, &[y2]));
+    }
+}
@@ -5,25 +5,26 @@ use crossterm::{
     style::{Color as TermColor, SetBackgroundColor},
 };
 use crate::facelet::{Color, FaceCube};
+use crate::scheme::{ColorScheme, StickerColor};
 
-fn color_to_termcolor(color: Color) -> TermColor {
+fn color_to_termcolor(color: StickerColor) -> TermColor {
     match color {
-        Color::U => TermColor::DarkYellow,
-        Color::R => TermColor::Magenta,
-        Color::F => TermColor::Green,
-        Color::D => TermColor::White,
-        Color::L => TermColor::Red,
-        Color::B => TermColor::Blue,
+        StickerColor::White => TermColor::White,
+        StickerColor::Yellow => TermColor::DarkYellow,
+        StickerColor::Green => TermColor::Green,
+        StickerColor::Blue => TermColor::Blue,
+        StickerColor::Red => TermColor::Red,
+        StickerColor::Orange => TermColor::Magenta,
     }
 }
 
-fn print_face(face: &[Color], offset: u16) -> Result<(), std::io::Error> {
+fn print_face(face: &[Color], offset: u16, scheme: &ColorScheme) -> Result<(), std::io::Error> {
     for i in 0..3 {
         let layer = format!(
             "{}  {}  {}  {}",
-            SetBackgroundColor(color_to_termcolor(face[3 * i])),
-            SetBackgroundColor(color_to_termcolor(face[(3 * i) + 1])),
-            SetBackgroundColor(color_to_termcolor(face[(3 * i) + 2])),
+            SetBackgroundColor(color_to_termcolor(scheme.color(face[3 * i]))),
+            SetBackgroundColor(color_to_termcolor(scheme.color(face[(3 * i) + 1]))),
+            SetBackgroundColor(color_to_termcolor(scheme.color(face[(3 * i) + 2]))),
             SetBackgroundColor(TermColor::Reset)
         );
 
@@ -37,22 +38,28 @@ fn print_face(face: &[Color], offset: u16) -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Print the facelet cube in the WCA colour scheme.
 pub fn print_facelet(facelet: &FaceCube) -> Result<(), std::io::Error> {
+    print_facelet_with(facelet, &ColorScheme::default())
+}
+
+/// Print the facelet cube in the colour `scheme`.
+pub fn print_facelet_with(facelet: &FaceCube, scheme: &ColorScheme) -> Result<(), std::io::Error> {
     let stdout = stdout();
 
     println!();
     execute!(&stdout, MoveRight(6))?;
-    print_face(&facelet.f[0..9], 6)?; // U (white)
+    print_face(&facelet.f[0..9], 6, scheme)?; // U
     execute!(&stdout, MoveLeft(6))?;
-    print_face(&facelet.f[36..45], 0)?; // L (orange)
+    print_face(&facelet.f[36..45], 0, scheme)?; // L
     execute!(&stdout, MoveRight(6), MoveUp(3))?;
-    print_face(&facelet.f[18..27], 6)?; // F (green)
+    print_face(&facelet.f[18..27], 6, scheme)?; // F
     execute!(&stdout, MoveLeft(12), MoveUp(3), MoveRight(12))?;
-    print_face(&facelet.f[9..18], 12)?; // R (red)
+    print_face(&facelet.f[9..18], 12, scheme)?; // R
     execute!(&stdout, MoveLeft(12), MoveUp(3), MoveRight(18))?;
-    print_face(&facelet.f[45..54], 18)?; // B (blue)
+    print_face(&facelet.f[45..54], 18, scheme)?; // B
     execute!(&stdout, MoveLeft(12))?;
-    print_face(&facelet.f[27..36], 6)?; // D (yellow)
+    print_face(&facelet.f[27..36], 6, scheme)?; // D
     execute!(&stdout, MoveLeft(12))?;
     println!();
 
@@ -74,5 +81,6 @@ mod tests {
         println!("{:#?}", &cc);
         let fc = FaceCube::try_from(&cc).unwrap();
         let _ = print_facelet(&fc);
+        let _ = print_facelet_with(&fc, &ColorScheme::japanese());
     }
-}
\ No newline at end of file
+}
\ No newline at end of file
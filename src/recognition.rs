@@ -0,0 +1,169 @@
+//! # Recognition
+//! Recognise the last layer case of a cube without solving it, i.e, the canonical case name (or number),
+//! the pre-AUF (U turn before the algorithm), the algorithm and the post-AUF (U turn after the algorithm).
+//! * OLL: the 57 cases numbered 1-57 (CFOP).
+//! * PLL: the 21 cases, eg. "T", "Ua", "Gc" (CFOP).
+//! * CMLL: the 42 cases, eg. "pi_right_bar" (Roux).
+//! * COLL: the 7 corner orientation cases once the edges are oriented, eg. "Sune" (LBL).
+//! * EPLL: the 4 edge permutation cases once the corners are permuted, "Ua", "Ub", "H", "Z" (LBL).
+//!
+//! A case already solved up to an AUF is named "skip".
+//! # Example
+//! ```rust
+//! use rcuber::cubie::CubieCube;
+//! use rcuber::moves::Move::*;
+//! use rcuber::recognition;
+//!
+//! fn main() {
+//!     let t_perm = vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3];
+//!     let cc = CubieCube::default().apply_moves(&[U]).apply_moves(&t_perm);
+//!     let case = recognition::pll(&cc).unwrap();
+//!     assert_eq!(case.name, "T");
+//!     assert_eq!(cc.apply_moves(&case.moves()), CubieCube::default());
+//!     println!("{:?}", case);
+//! }
+//! ```
+
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::moves::Move;
+use crate::solver::cfop::{OLLSolver, PLLSolver};
+use crate::solver::lbl::check_steps;
+use crate::solver::roux::CMLLSolver;
+use crate::solver::{auf_moves, stage_error};
+
+/// A recognised case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    /// Canonical name or number of the case, eg. "27", "T", "pi_right_bar", or "skip".
+    pub name: String,
+    /// U turn to do before the algorithm.
+    pub pre_auf: Option<Move>,
+    /// Algorithm solving the case.
+    pub algorithm: Vec<Move>,
+    /// U turn to do after the algorithm.
+    pub post_auf: Option<Move>,
+}
+
+impl Case {
+    fn new(name: &str, pre_auf: usize, algorithm: Vec<Move>, post_auf: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            pre_auf: auf_moves(pre_auf).first().copied(),
+            algorithm,
+            post_auf: auf_moves(post_auf).first().copied(),
+        }
+    }
+
+    /// All the moves solving the case: pre-AUF, algorithm and post-AUF.
+    pub fn moves(&self) -> Vec<Move> {
+        let mut moves: Vec<Move> = self.pre_auf.into_iter().collect();
+        moves.extend(&self.algorithm);
+        moves.extend(self.post_auf);
+        moves
+    }
+}
+
+/// Recognise the OLL case, the name is the OLL number. The F2L must be solved.
+pub fn oll(cube: &CubieCube) -> Result<Case, Error> {
+    let (pre, number, algo) = OLLSolver::new(*cube).find_case()?;
+    match number {
+        0 => Ok(Case::new("skip", 0, algo, 0)),
+        _ => Ok(Case::new(&number.to_string(), pre, algo, 0)),
+    }
+}
+
+/// Recognise the PLL case. The F2L and OLL must be solved.
+pub fn pll(cube: &CubieCube) -> Result<Case, Error> {
+    let (pre, name, algo, post) = PLLSolver::new(*cube).find_case()?;
+    Ok(Case::new(name, pre, algo, post))
+}
+
+/// Recognise the CMLL case. The First and Second Block must be solved.
+pub fn cmll(cube: &CubieCube) -> Result<Case, Error> {
+    let (pre, name, algo, post) = CMLLSolver::new(*cube).find_case()?;
+    Ok(Case::new(name, pre, algo, post))
+}
+
+/// Recognise the COLL (corner orientation of last layer) case. The first two layers and the last layer's edge orientation must be solved.
+pub fn coll(cube: &CubieCube) -> Result<Case, Error> {
+    check_steps("COLL", cube, 4)?;
+    let (pre, number, algo) = OLLSolver::new(*cube).find_case()?;
+    let name = match number {
+        0 => "skip",
+        21 => "H",
+        22 => "Pi",
+        23 => "U",
+        24 => "T",
+        25 => "L",
+        26 => "Antisune",
+        27 => "Sune",
+        _ => return Err(stage_error("COLL", "unrecognised COLL case")),
+    };
+    Ok(Case::new(name, pre, algo, 0))
+}
+
+/// Recognise the EPLL (edge permutation of last layer) case. The last layer's corners must be permuted.
+pub fn epll(cube: &CubieCube) -> Result<Case, Error> {
+    let (pre, name, algo, post) = PLLSolver::new(*cube).find_case()?;
+    match name {
+        "skip" | "Ua" | "Ub" | "H" | "Z" => Ok(Case::new(name, pre, algo, post)),
+        _ => Err(stage_error("EPLL", "CPLL is not solved")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moves::Move::*;
+
+    #[test]
+    fn test_oll() {
+        let cc = CubieCube::default().apply_moves(&[R, U2, R3, U3, R, U3, R3, U2]);
+        let case = oll(&cc).unwrap();
+        assert_eq!(case.name, "27");
+        assert!(OLLSolver::new(cc.apply_moves(&case.moves())).is_solved());
+        assert_eq!(oll(&CubieCube::default()).unwrap().name, "skip");
+        assert!(oll(&CubieCube::default().apply_moves(&[R])).is_err());
+    }
+
+    #[test]
+    fn test_pll() {
+        let ua = vec![R, U3, R, U, R, U, R, U3, R3, U3, R2];
+        for pre in [vec![], vec![U], vec![U2], vec![U3]] {
+            for post in [vec![], vec![U], vec![U2], vec![U3]] {
+                let cc = CubieCube::default()
+                    .apply_moves(&pre)
+                    .apply_moves(&ua)
+                    .apply_moves(&post);
+                let case = pll(&cc).unwrap();
+                assert_eq!(case.name, "Ub");
+                assert_eq!(cc.apply_moves(&case.moves()), CubieCube::default());
+                assert_eq!(epll(&cc).unwrap().name, "Ub");
+            }
+        }
+        let case = pll(&CubieCube::default().apply_move(U2)).unwrap();
+        assert_eq!(case.name, "skip");
+        assert_eq!(case.moves(), vec![U2]);
+        let t_perm = vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3];
+        assert!(epll(&CubieCube::default().apply_moves(&t_perm)).is_err());
+    }
+
+    #[test]
+    fn test_cmll() {
+        let cc = CubieCube::default().apply_moves(&[F, R, U, R3, U3, R, U, R3, U3, F3, U]);
+        let case = cmll(&cc).unwrap();
+        assert!(case.name.starts_with("pi_"));
+        assert!(CMLLSolver::new(cc.apply_moves(&case.moves())).is_solved());
+    }
+
+    #[test]
+    fn test_coll() {
+        let cc = CubieCube::default().apply_moves(&[R, U2, R3, U3, R, U3, R3]);
+        assert_eq!(coll(&cc).unwrap().name, "Sune");
+        let cc = CubieCube::default().apply_moves(&[R, U, R3, U, R, U2, R3]);
+        assert_eq!(coll(&cc).unwrap().name, "Antisune");
+        let cc = CubieCube::default().apply_moves(&[F, R, U, R3, U3, F3]);
+        assert!(coll(&cc).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! # Colour scheme
+//! `Color` names the faces (U, R, F, D, L, B), a `ColorScheme` maps them to the physical sticker colours:
+//! * WCA Western: white U, yellow D, green F, blue B, red R, orange L.
+//! * Japanese: the Western scheme with blue and yellow swapped, i.e, white U, blue D, green F, yellow B.
+//! * Custom: any assignment of the 6 colours to the faces.
+//!
+//! The colours are written by their letters: W(hite), Y(ellow), G(reen), B(lue), R(ed), O(range).
+//! # Example
+//! ```rust
+//! use rcuber::cubie::CubieCube;
+//! use rcuber::facelet::FaceCube;
+//! use rcuber::moves::Move;
+//! use rcuber::scheme::ColorScheme;
+//!
+//! fn main() {
+//!     let scheme = ColorScheme::western();
+//!     let fc = FaceCube::try_from(&CubieCube::default().apply_move(Move::R)).unwrap();
+//!     let s = fc.to_colors(&scheme);
+//!     assert_eq!(&s[..9], "WWGWWGWWG");
+//!     assert_eq!(FaceCube::from_colors(&s, &scheme).unwrap(), fc);
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::constants::ALL_COLORS;
+use crate::error::Error;
+use crate::facelet::{Color, FaceCube};
+
+/// The physical colours of the stickers.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StickerColor {
+    White,
+    Yellow,
+    Green,
+    Blue,
+    Red,
+    Orange,
+}
+
+impl StickerColor {
+    /// The letter of the colour, eg. 'W' for white.
+    pub fn letter(self) -> char {
+        match self {
+            StickerColor::White => 'W',
+            StickerColor::Yellow => 'Y',
+            StickerColor::Green => 'G',
+            StickerColor::Blue => 'B',
+            StickerColor::Red => 'R',
+            StickerColor::Orange => 'O',
+        }
+    }
+}
+
+impl fmt::Display for StickerColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+impl TryFrom<char> for StickerColor {
+    type Error = Error;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'W' => Ok(StickerColor::White),
+            'Y' => Ok(StickerColor::Yellow),
+            'G' => Ok(StickerColor::Green),
+            'B' => Ok(StickerColor::Blue),
+            'R' => Ok(StickerColor::Red),
+            'O' => Ok(StickerColor::Orange),
+            _ => Err(Error::InvalidColor),
+        }
+    }
+}
+
+/// The sticker colours of the faces, in U-R-F-D-L-B order.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "[StickerColor; 6]", into = "[StickerColor; 6]")
+)]
+pub struct ColorScheme {
+    colors: [StickerColor; 6],
+}
+
+impl ColorScheme {
+    /// A custom scheme of the colours of the U, R, F, D, L, B faces, each colour is used once.
+    pub fn new(colors: [StickerColor; 6]) -> Result<Self, Error> {
+        for (i, c) in colors.iter().enumerate() {
+            if colors[..i].contains(c) {
+                return Err(Error::InvalidColor);
+            }
+        }
+        Ok(Self { colors })
+    }
+
+    /// The WCA scheme: white U, red R, green F, yellow D, orange L, blue B.
+    pub fn western() -> Self {
+        use StickerColor::*;
+        Self {
+            colors: [White, Red, Green, Yellow, Orange, Blue],
+        }
+    }
+
+    /// The Japanese scheme: white U, red R, green F, blue D, orange L, yellow B.
+    pub fn japanese() -> Self {
+        use StickerColor::*;
+        Self {
+            colors: [White, Red, Green, Blue, Orange, Yellow],
+        }
+    }
+
+    /// The sticker colour of the `face`.
+    pub fn color(&self, face: Color) -> StickerColor {
+        self.colors[face as usize]
+    }
+
+    /// The face of the sticker `color`.
+    pub fn face(&self, color: StickerColor) -> Result<Color, Error> {
+        let i = self
+            .colors
+            .iter()
+            .position(|c| *c == color)
+            .ok_or(Error::InvalidColor)?;
+        Ok(ALL_COLORS[i])
+    }
+}
+
+impl TryFrom<[StickerColor; 6]> for ColorScheme {
+    type Error = Error;
+    fn try_from(colors: [StickerColor; 6]) -> Result<Self, Self::Error> {
+        Self::new(colors)
+    }
+}
+
+impl From<ColorScheme> for [StickerColor; 6] {
+    fn from(scheme: ColorScheme) -> Self {
+        scheme.colors
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::western()
+    }
+}
+
+impl FaceCube {
+    /// Parse a facelet string of the sticker colours, eg. "WWWWWWWWWRRR...", under the `scheme`.
+    pub fn from_colors(s: &str, scheme: &ColorScheme) -> Result<Self, Error> {
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() != 54 {
+            return Err(Error::InvalidFaceletString);
+        }
+        let mut face_cube = FaceCube::default();
+        for (i, c) in chars.into_iter().enumerate() {
+            face_cube.f[i] = scheme.face(StickerColor::try_from(c)?)?;
+        }
+        Ok(face_cube)
+    }
+
+    /// The facelet string of the sticker colours under the `scheme`.
+    pub fn to_colors(&self, scheme: &ColorScheme) -> String {
+        self.f.iter().map(|f| scheme.color(*f).letter()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubie::CubieCube;
+    use crate::moves::Formula;
+    use StickerColor::*;
+
+    #[test]
+    fn test_schemes() {
+        let solved = FaceCube::default();
+        assert_eq!(
+            solved.to_colors(&ColorScheme::western()),
+            "WWWWWWWWWRRRRRRRRRGGGGGGGGGYYYYYYYYYOOOOOOOOOBBBBBBBBB"
+        );
+        assert_eq!(
+            solved.to_colors(&ColorScheme::japanese()),
+            "WWWWWWWWWRRRRRRRRRGGGGGGGGGBBBBBBBBBOOOOOOOOOYYYYYYYYY"
+        );
+        let scheme = ColorScheme::new([Yellow, Orange, Blue, White, Red, Green]).unwrap();
+        assert_eq!(scheme.face(Yellow).unwrap(), Color::U);
+        assert_eq!(scheme.color(Color::L), Red);
+        assert!(ColorScheme::new([Yellow, Orange, Blue, White, Red, Red]).is_err());
+
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        let fc = FaceCube::try_from(&cc).unwrap();
+        for scheme in [ColorScheme::western(), ColorScheme::japanese(), scheme] {
+            let s = fc.to_colors(&scheme);
+            assert_eq!(FaceCube::from_colors(&s, &scheme).unwrap(), fc);
+        }
+        assert!(FaceCube::from_colors("WWW", &ColorScheme::default()).is_err());
+        let s = solved.to_colors(&ColorScheme::western()).replace('O', "P");
+        assert!(FaceCube::from_colors(&s, &ColorScheme::western()).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let scheme = ColorScheme::japanese();
+        let json = serde_json::to_string(&scheme).unwrap();
+        assert_eq!(json, r#"["White","Red","Green","Blue","Orange","Yellow"]"#);
+        assert_eq!(serde_json::from_str::<ColorScheme>(&json).unwrap(), scheme);
+        let json = r#"["White","Red","Green","Blue","Orange","White"]"#;
+        assert!(serde_json::from_str::<ColorScheme>(json).is_err());
+    }
+}
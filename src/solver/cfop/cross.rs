@@ -5,8 +5,10 @@ use crate::cubie::{
     CubieCube,
     Edge::{self, *},
 };
+use crate::error::Error;
 use crate::facelet::Color;
 use crate::moves::Move::{self, *};
+use crate::solver::{check_cube_centers, stage_error};
 
 use super::{a_star_search, edge_to_pos};
 
@@ -24,7 +26,7 @@ use super::{a_star_search, edge_to_pos};
 ///     let cc = cc.apply_formula(&formula);
 ///     let mut cross = CrossSolver{cube: cc};
 ///     assert!(!cross.is_solved());
-///     let solution = cross.solve();
+///     let solution = cross.solve().unwrap();
 ///     assert!(cross.is_solved());
 ///     println!("Cross Solution: {:?}", solution);
 /// }
@@ -213,7 +215,8 @@ impl CrossSolver {
     }
 
     /// Solve the cross.
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_cube_centers("Cross", &self.cube)?;
         let solution = a_star_search(
             &self.cube,
             CrossSolver::cross_successors,
@@ -221,7 +224,10 @@ impl CrossSolver {
             CrossSolver::cross_goal,
         );
         self.cube = self.cube.apply_moves(&solution);
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("Cross", "no solution found"));
+        }
+        Ok(solution)
     }
 
     pub fn is_solved(&self) -> bool {
@@ -241,7 +247,7 @@ mod tests {
         let moves = vec![L2, R, F2, L, D, U, R, L, D, F, U];
         let cc = cc.apply_moves(&moves);
         let mut cs = CrossSolver { cube: cc };
-        let result = cs.solve();
+        let result = cs.solve().unwrap();
         let cc = cc.apply_moves(&result);
         let solved = CrossSolver::cross_goal(&cc);
         assert!(solved);
@@ -249,7 +255,7 @@ mod tests {
         let formula = Formula::scramble();
         let cc = cc.apply_formula(&formula);
         let mut cs = CrossSolver { cube: cc };
-        let solution = cs.solve();
+        let solution = cs.solve().unwrap();
         let cc = cc.apply_moves(&solution);
         let solved = CrossSolver::cross_goal(&cc);
         assert!(solved);
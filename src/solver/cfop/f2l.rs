@@ -1,12 +1,14 @@
 use crate::{
     cubie::{Corner, CubieCube, Edge},
+    error::Error,
     facelet::Color,
     moves::Formula,
     moves::Move::{self, *},
+    solver::{check_cube_centers, check_solved, stage_error},
 };
 use std::collections::HashMap;
 
-use super::{corner_to_pos, correct_slot, edge_to_pos};
+use super::{corner_to_pos, correct_slot, edge_to_pos, CrossSolver};
 
 /// CrossSolver for solve CFOP's F2L. MUST SOLVE CROSS FIRST!!
 /// # Example
@@ -23,12 +25,12 @@ use super::{corner_to_pos, correct_slot, edge_to_pos};
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver{cube: cc};
 ///     assert!(!cross.is_solved());
-///     let solution = cross.solve();
+///     let solution = cross.solve().unwrap();
 ///     assert!(cross.is_solved());
 ///     println!("Cross Solution: {:?}", solution);
 ///     let mut f2l = F2LSolver{cube: cross.cube};
 ///     assert!(!f2l.is_solved());
-///     let solution = f2l.solve();
+///     let solution = f2l.solve().unwrap();
 ///     assert!(f2l.is_solved());
 ///     println!("F2L Solution: {:?}", solution);
 /// }
@@ -39,7 +41,15 @@ pub struct F2LSolver {
 
 impl F2LSolver {
     /// Solve the entire F2L.
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        let pairs = self.solve_pairs()?;
+        Ok(pairs.into_iter().flat_map(|(_, moves)| moves).collect())
+    }
+
+    /// Solve the F2L pair by pair. Returns the slot (eg. "FR") and the moves of each pair, in solving order.
+    pub fn solve_pairs(&mut self) -> Result<Vec<(String, Vec<Move>)>, Error> {
+        check_cube_centers("F2L", &self.cube)?;
+        check_solved("F2L", &[("Cross", CrossSolver { cube: self.cube }.is_solved())])?;
         let mut solution = Vec::new();
         let mut slots_type = Vec::new();
         for slot in [
@@ -60,13 +70,17 @@ impl F2LSolver {
                 cube: self.cube,
                 pair: p.0,
             };
+            let mut pair_result = Vec::new();
             if !solver.is_solved() {
-                let mut pair_result = solver.solve();
+                pair_result = solver.solve();
                 self.cube = self.cube.apply_moves(&pair_result);
-                solution.append(&mut pair_result);
             }
+            solution.push((format!("{:?}{:?}", p.0[0], p.0[1]), pair_result));
+        }
+        if !self.is_solved() {
+            return Err(stage_error("F2L", "no solution found"));
         }
-        solution
+        Ok(solution)
     }
 
     /// Check if Cube's F2L is solved.
@@ -608,11 +622,11 @@ mod tests {
         let moves = vec![L2, R, F2, L, D, U, R, D, B3];
         let cc = cc.apply_moves(&moves);
         let mut cross = CrossSolver { cube: cc };
-        let _c = cross.solve();
+        let _c = cross.solve().unwrap();
         println!("{:?}", _c);
         let cc = cross.cube.clone();
         let mut f2l = F2LSolver { cube: cc };
-        let _f = f2l.solve();
+        let _f = f2l.solve().unwrap();
         println!("{:?}", _f);
     }
 }
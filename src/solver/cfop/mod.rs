@@ -25,8 +25,10 @@ pub use pll::PLLSolver;
 
 use crate::{
     cubie::{Corner, CubieCube, Edge},
+    error::Error,
     facelet::Color,
-    moves::Move,
+    moves::{Formula, Move},
+    solver::{Solution, SolveReport, Solver},
 };
 
 /// Module for CFOP's first step, solving Rubik's Cube Cross.
@@ -43,15 +45,16 @@ pub mod pll;
 /// ```rust
 /// use rcuber::cubie::CubieCube;
 /// use rcuber::moves::Formula;
+/// use rcuber::solver::Solver;
 /// use rcuber::solver::cfop::CFOPSolver;
 ///
 /// fn main() {
 ///     let cc = CubieCube::default();
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
-///     let mut solver = CFOPSolver{cube: cc};
+///     let mut solver = CFOPSolver::new(cc);
 ///     assert!(!solver.is_solved());
-///     let solution = solver.solve();
+///     let solution = solver.solve().unwrap();
 ///     assert!(solver.is_solved());
 ///     println!("Scramble: {:?}\nSolution: {:?}", moves, solution);
 /// }
@@ -60,39 +63,58 @@ pub struct CFOPSolver {
     pub cube: CubieCube,
 }
 
+impl Solver for CFOPSolver {
+    fn new(cube: CubieCube) -> Self {
+        Self { cube }
+    }
+
+    /// Solve the cube, step by step: Cross, F2L, OLL, PLL. The moves are cancelled across the stages.
+    fn solve(&mut self) -> Result<Solution, Error> {
+        let solution = Formula::from(self.solve_report()?.solution());
+        Ok(Solution::from(solution.optimise()))
+    }
+
+    fn is_solved(&self) -> bool {
+        self.cube == CubieCube::default()
+    }
+
+    fn name(&self) -> &'static str {
+        "CFOP"
+    }
+}
+
 impl CFOPSolver {
-    pub fn solve(&mut self) -> Vec<Move> {
-        let mut solution = Vec::new();
+    /// Solve the cube and report each stage: Cross, the four F2L pairs, OLL and PLL with their cases.
+    pub fn solve_report(&mut self) -> Result<SolveReport, Error> {
+        let mut report = SolveReport::default();
 
         let mut cross = CrossSolver { cube: self.cube };
-        let mut cs = cross.solve();
-        assert!(cross.is_solved());
+        let cs = cross.solve()?;
         self.cube = cross.cube;
-        solution.append(&mut cs);
+        report.push("Cross".to_string(), cs, None, self.cube);
 
         let mut f2l = F2LSolver { cube: self.cube };
-        let mut fs = f2l.solve();
-        assert!(f2l.is_solved());
-        self.cube = f2l.cube;
-        solution.append(&mut fs);
+        for (slot, fs) in f2l.solve_pairs()? {
+            self.cube = self.cube.apply_moves(&fs);
+            report.push(format!("F2L pair {}", slot), fs, None, self.cube);
+        }
 
         let mut oll = OLLSolver::new(self.cube);
-        let mut os = oll.solve();
-        assert!(oll.is_solved());
+        let os = oll.solve()?;
         self.cube = oll.cube;
-        solution.append(&mut os);
+        let case = match oll.case() {
+            Some(0) | None => "skip".to_string(),
+            Some(n) => n.to_string(),
+        };
+        report.push(format!("OLL {}", case), os, Some(case), self.cube);
 
         let mut pll = PLLSolver::new(self.cube);
-        let mut ps = pll.solve();
-        assert!(pll.is_solved());
+        let ps = pll.solve()?;
         self.cube = pll.cube;
-        solution.append(&mut ps);
+        let case = pll.case().unwrap_or("skip").to_string();
+        report.push(format!("PLL {}", case), ps, Some(case), self.cube);
 
-        solution
-    }
-
-    pub fn is_solved(&self) -> bool {
-        self.cube == CubieCube::default()
+        Ok(report)
     }
 }
 
@@ -217,7 +239,12 @@ fn corner_to_pos(corner: (Corner, u8, u8)) -> HashMap<Color, Color> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{cubie::CubieCube, moves::Formula, solver::CFOPSolver};
+    use crate::{
+        cubie::CubieCube,
+        error::Error,
+        moves::{Formula, Move::*},
+        solver::{CFOPSolver, Solver},
+    };
 
     #[test]
     fn test_cfop() {
@@ -225,12 +252,68 @@ mod tests {
         let moves = Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let cc2 = cc.clone();
-        let mut solver = CFOPSolver { cube: cc };
-        let solution = solver.solve();
+        let mut solver = CFOPSolver::new(cc);
+        let solution = solver.solve().unwrap();
         assert!(solver.is_solved());
 
-        let cc2 = cc2.apply_moves(&solution);
+        let cc2 = cc2.apply_moves(&solution.moves);
         assert_eq!(cc2, CubieCube::default());
         println!("Scramble: {:?}\nSolution: {:?}", moves, solution);
     }
+
+    #[test]
+    fn test_cfop_report() {
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        let mut solver = CFOPSolver::new(cc);
+        let report = solver.solve_report().unwrap();
+        let names: Vec<&str> = report.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names.len(), 7);
+        assert_eq!(names[0], "Cross");
+        assert!(names[1..5].iter().all(|n| n.starts_with("F2L pair ")));
+        assert!(names[5].starts_with("OLL ") && names[6].starts_with("PLL "));
+        let mut cube = cc;
+        for stage in &report.stages {
+            cube = cube.apply_moves(&stage.moves);
+            assert_eq!(cube, stage.cube);
+        }
+        assert_eq!(cube, CubieCube::default());
+
+        let t_perm = vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3];
+        let mut solver = CFOPSolver::new(CubieCube::default().apply_moves(&t_perm));
+        let report = solver.solve_report().unwrap();
+        assert_eq!(report.stages[5].name, "OLL skip");
+        assert_eq!(report.stages[6].name, "PLL T");
+        assert_eq!(report.stages[6].case, Some("T".to_string()));
+
+        let anti_sune = vec![R, U2, R3, U3, R, U3, R3];
+        let mut solver = CFOPSolver::new(CubieCube::default().apply_moves(&anti_sune));
+        let report = solver.solve_report().unwrap();
+        assert_eq!(report.stages[5].name, "OLL 27");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_cfop_report_serde() {
+        use crate::solver::{Solution, SolveReport};
+
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        let report = CFOPSolver::new(cc).solve_report().unwrap();
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<SolveReport>(&json).unwrap(), report);
+        let solution = report.solution();
+        let json = serde_json::to_string(&solution).unwrap();
+        assert!(json.contains(&format!("\"{}\"", solution.moves[0])));
+        assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), solution);
+    }
+
+    #[test]
+    fn test_cfop_invalid_cube() {
+        let mut cc = CubieCube::default();
+        cc.co[0] = 1;
+        let mut solver = CFOPSolver::new(cc);
+        match solver.solve() {
+            Err(Error::StageFailed { stage, .. }) => assert_eq!(stage, "Cross"),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+    }
 }
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 use crate::cubie::CubieCube;
+use crate::error::Error;
 use crate::moves::Move::{self, *};
+use crate::solver::{auf_moves, check_cube_centers, check_solved, stage_error};
+
+use super::F2LSolver;
 
 /// CrossSolver for solve CFOP's OLL. MUST HAVE SOLVED CROSS & F2L!!
 /// # Example
@@ -18,269 +22,278 @@ use crate::moves::Move::{self, *};
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver{cube: cc};
 ///     assert!(!cross.is_solved());
-///     let solution = cross.solve();
+///     let solution = cross.solve().unwrap();
 ///     assert!(cross.is_solved());
 ///     println!("Cross Solution: {:?}", solution);
 ///     let mut f2l = F2LSolver{cube: cross.cube};
 ///     assert!(!f2l.is_solved());
-///     let solution = f2l.solve();
+///     let solution = f2l.solve().unwrap();
 ///     assert!(f2l.is_solved());
 ///     println!("F2L Solution: {:?}", solution);
 ///     let mut oll = OLLSolver::new(f2l.cube);
-///     let solution = oll.solve();
+///     let solution = oll.solve().unwrap();
 ///     assert!(oll.is_solved());
-///     println!("OLL Solution: {:?}", solution);
+///     println!("OLL {:?} Solution: {:?}", oll.case(), solution);
 /// }
 /// ```
 pub struct OLLSolver {
     pub cube: CubieCube,
-    algos: HashMap<[u8; 12], Vec<Move>>,
+    algos: HashMap<[u8; 12], (u8, Vec<Move>)>,
+    case: Option<u8>,
 }
 
 impl OLLSolver {
     /// Construct the OLLSolver.
     pub fn new(cube: CubieCube) -> Self {
         let mut algos = HashMap::new();
-        // OLL1
         algos.insert(
             [1u8, 1, 1, 0, 1, 0, 1, 1, 1, 0, 1, 0],
-            vec![R, U2, R2, F, R, F3, U2, R3, F, R, F3],
+            (1, vec![R, U2, R2, F, R, F3, U2, R3, F, R, F3]),
         );
         algos.insert(
             [0, 1, 0, 1, 1, 0, 1, 1, 1, 0, 1, 1],
-            vec![F, R, U, R3, U3, F3, Fw, R, U, R3, U3, Fw3],
+            (2, vec![F, R, U, R3, U3, F3, Fw, R, U, R3, U3, Fw3]),
         );
         algos.insert(
             [1, 1, 0, 0, 1, 0, 1, 1, 0, 1, 1, 0],
-            vec![Fw, R, U, R3, U3, Fw3, U3, F, R, U, R3, U3, F3],
+            (3, vec![Fw, R, U, R3, U3, Fw3, U3, F, R, U, R3, U3, F3]),
         );
         algos.insert(
             [0, 1, 1, 0, 1, 1, 0, 1, 1, 0, 1, 0],
-            vec![Fw, R, U, R3, U3, Fw3, U, F, R, U, R3, U3, F3],
+            (4, vec![Fw, R, U, R3, U3, Fw3, U, F, R, U, R3, U3, F3]),
         );
         algos.insert(
             [1, 0, 0, 0, 0, 0, 1, 1, 0, 1, 1, 0],
-            vec![Rw3, U2, R, U, R3, U, Rw],
+            (5, vec![Rw3, U2, R, U, R3, U, Rw]),
         );
         algos.insert(
             [0, 1, 1, 0, 0, 0, 0, 0, 1, 0, 1, 1],
-            vec![Lw, U2, L3, U3, L, U3, Lw3],
+            (6, vec![Lw, U2, L3, U3, L, U3, Lw3]),
         );
         algos.insert(
             [0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 1, 0],
-            vec![Lw, U, L3, U, L, U2, Lw3],
+            (7, vec![Lw, U, L3, U, L, U2, Lw3]),
         );
         algos.insert(
             [0, 1, 1, 0, 0, 1, 0, 0, 0, 0, 1, 1],
-            vec![Rw3, U3, R, U3, R3, U2, Rw],
+            (8, vec![Rw3, U3, R, U3, R3, U2, Rw]),
         );
         algos.insert(
             [0, 1, 0, 0, 1, 1, 0, 0, 1, 0, 0, 1],
-            vec![R, U, R3, U3, R3, F, R2, U, R3, U3, F3],
+            (9, vec![R, U, R3, U3, R3, F, R2, U, R3, U3, F3]),
         );
         algos.insert(
             [0, 1, 0, 1, 0, 0, 1, 0, 0, 1, 1, 0],
-            vec![R, U, R3, U, R3, F, R, F3, R, U2, R3],
+            (10, vec![R, U, R3, U, R3, F, R, F3, R, U2, R3]),
         );
-        // OLL11
         algos.insert(
             [1, 1, 0, 1, 0, 0, 1, 0, 0, 0, 1, 0],
-            vec![F3, L3, U3, L, U, F, y, F, R, U, R3, U3, F3, y3],
+            (11, vec![F3, L3, U3, L, U, F, y, F, R, U, R3, U3, F3, y3]),
         );
         algos.insert(
             [0, 0, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0],
-            vec![F, R, U, R3, U3, F3, U, F, R, U, R3, U3, F3],
+            (12, vec![F, R, U, R3, U3, F3, U, F, R, U, R3, U3, F3]),
         );
         algos.insert(
             [1, 0, 0, 1, 1, 0, 0, 0, 0, 1, 1, 0],
-            vec![Rw, U3, Rw3, U3, Rw, U, Rw3, y3, R3, U, R, y],
+            (13, vec![Rw, U3, Rw3, U3, Rw, U, Rw3, y3, R3, U, R, y]),
         );
         algos.insert(
             [0, 0, 0, 0, 1, 1, 0, 0, 1, 0, 1, 1],
-            vec![R3, F, R, U, R3, F3, R, y3, R, U3, R3, y],
+            (14, vec![R3, F, R, U, R3, F3, R, y3, R, U3, R3, y]),
         );
         algos.insert(
             [1, 0, 0, 1, 1, 0, 1, 0, 0, 0, 1, 0],
-            vec![Lw3, U3, Lw, L3, U3, L, U, Lw3, U, Lw],
+            (15, vec![Lw3, U3, Lw, L3, U3, L, U, Lw3, U, Lw]),
         );
         algos.insert(
             [0, 0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0],
-            vec![Rw, U, Rw3, R, U, R3, U3, Rw, U3, Rw3],
+            (16, vec![Rw, U, Rw3, R, U, R3, U3, Rw, U3, Rw3]),
         );
         algos.insert(
             [0, 1, 0, 0, 1, 0, 1, 1, 0, 0, 1, 1],
-            vec![R, U, R3, U, R3, F, R, F3, U2, R3, F, R, F3],
+            (17, vec![R, U, R3, U, R3, F, R, F3, U2, R3, F, R, F3]),
         );
         algos.insert(
             [0, 1, 0, 0, 1, 0, 0, 1, 0, 1, 1, 1],
-            vec![F, R, U, R3, U, y3, R3, U2, R3, F, R, F3, y],
+            (18, vec![F, R, U, R3, U, y3, R3, U2, R3, F, R, F3, y]),
         );
         algos.insert(
             [0, 1, 1, 0, 1, 0, 1, 1, 0, 0, 1, 0],
-            vec![Rw3, R, U, R, U, R3, U3, Rw, R2, F, R, F3],
+            (19, vec![Rw3, R, U, R, U, R3, U3, Rw, R2, F, R, F3]),
         );
         algos.insert(
             [0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0],
-            vec![Rw3, R, U, R, U, R3, U3, M2, U, R, U3, Rw3],
+            (20, vec![Rw3, R, U, R, U, R3, U3, M2, U, R, U3, Rw3]),
         );
-        // OLL21
         algos.insert(
             [0, 0, 0, 1, 0, 1, 0, 0, 0, 1, 0, 1],
-            vec![R, U2, R3, U3, R, U, R3, U3, R, U3, R3],
+            (21, vec![R, U2, R3, U3, R, U, R3, U3, R, U3, R3]),
         );
         algos.insert(
             [0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0, 1],
-            vec![R, U2, R2, U3, R2, U3, R2, U2, R],
+            (22, vec![R, U2, R2, U3, R2, U3, R2, U2, R]),
         );
         algos.insert(
             [0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1],
-            vec![R2, D3, R, U2, R3, D, R, U2, R],
+            (23, vec![R2, D3, R, U2, R3, D, R, U2, R]),
         );
         algos.insert(
             [0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0],
-            vec![Rw, U, R3, U3, Rw3, F, R, F3],
+            (24, vec![Rw, U, R3, U3, Rw3, F, R, F3]),
         );
         algos.insert(
             [0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0],
-            vec![F3, Rw, U, R3, U3, Rw3, F, R],
+            (25, vec![F3, Rw, U, R3, U3, Rw3, F, R]),
         );
         algos.insert(
             [0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 0],
-            vec![R, U2, R3, U3, R, U3, R3],
+            (26, vec![R, U2, R3, U3, R, U3, R3]),
         );
         algos.insert(
             [1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0],
-            vec![R, U, R3, U, R, U2, R3],
+            (27, vec![R, U, R3, U, R, U2, R3]),
         );
         algos.insert(
             [0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0],
-            vec![Rw, U, R3, U3, Rw3, R, U, R, U3, R3],
+            (28, vec![Rw, U, R3, U3, Rw3, R, U, R, U3, R3]),
         );
         algos.insert(
             [0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0],
-            vec![Fw3, L3, U3, L2, U, L, U3, L2, U, L, Fw],
+            (29, vec![Fw3, L3, U3, L2, U, L, U3, L2, U, L, Fw]),
         );
         algos.insert(
             [0, 0, 1, 0, 0, 0, 1, 1, 0, 0, 1, 0],
-            vec![Fw, R, U, R2, U3, R3, U, R2, U3, R3, Fw3],
+            (30, vec![Fw, R, U, R2, U3, R3, U, R2, U3, R3, Fw3]),
         );
-        // OLL31
         algos.insert(
             [0, 0, 0, 0, 1, 1, 0, 1, 0, 1, 0, 0],
-            vec![R3, U3, F, U, R, U3, R3, F3, R],
+            (31, vec![R3, U3, F, U, R, U3, R3, F3, R]),
         );
         algos.insert(
             [0, 0, 0, 0, 0, 1, 0, 1, 0, 1, 1, 0],
-            vec![R, Dw, L3, Dw3, R3, U, Lw, U, Lw3],
+            (32, vec![R, Dw, L3, Dw3, R3, U, Lw, U, Lw3]),
         );
         algos.insert(
             [0, 0, 0, 0, 1, 1, 0, 0, 0, 1, 1, 0],
-            vec![R, U, R3, U3, R3, F, R, F3],
+            (33, vec![R, U, R3, U3, R3, F, R, F3]),
         );
         algos.insert(
             [1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 1, 0],
-            vec![R, U, R2, U3, R3, F, R, U, R, U3, F3],
+            (34, vec![R, U, R2, U3, R3, F, R, U, R, U3, F3]),
         );
         algos.insert(
             [1, 0, 0, 0, 0, 1, 0, 1, 0, 0, 1, 0],
-            vec![R, U2, R2, F, R, F3, R, U2, R3],
+            (35, vec![R, U2, R2, F, R, F3, R, U2, R3]),
         );
         algos.insert(
             [0, 0, 0, 0, 1, 0, 1, 1, 0, 0, 0, 1],
-            vec![L3, U3, L, U3, L3, U, L, U, L, F3, L3, F],
+            (36, vec![L3, U3, L, U3, L3, U, L, U, L, F3, L3, F]),
         );
         algos.insert(
             [1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0],
-            vec![F, R, U3, R3, U3, R, U, R3, F3],
+            (37, vec![F, R, U3, R3, U3, R, U, R3, F3]),
         );
         algos.insert(
             [0, 1, 1, 0, 1, 0, 0, 0, 0, 1, 0, 0],
-            vec![R, U, R3, U, R, U3, R3, U3, R3, F, R, F3],
+            (38, vec![R, U, R3, U, R, U3, R3, U3, R3, F, R, F3]),
         );
         algos.insert(
             [0, 0, 1, 0, 1, 0, 0, 0, 0, 1, 1, 0],
-            vec![L, F3, L3, U3, L, U, F, U3, L3],
+            (39, vec![L, F3, L3, U3, L, U, F, U3, L3]),
         );
         algos.insert(
             [0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 1, 1],
-            vec![R3, F, R, U, R3, U3, F3, U, R],
+            (40, vec![R3, F, R, U, R3, U3, F3, U, R]),
         );
-        //41
         algos.insert(
             [0, 0, 0, 1, 0, 1, 0, 1, 0, 0, 1, 0],
-            vec![R, U3, R3, U2, R, U, y, R, U3, R3, U3, F3, y3],
+            (41, vec![R, U3, R3, U2, R, U, y, R, U3, R3, U3, F3, y3]),
         );
         algos.insert(
             [0, 1, 0, 1, 0, 1, 0, 0, 0, 0, 1, 0],
-            vec![L3, U, L, U2, L3, U3, y3, L3, U, L, U, F, y],
+            (42, vec![L3, U, L, U2, L3, U3, y3, L3, U, L, U, F, y]),
         );
         algos.insert(
             [1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0],
-            vec![Fw3, L3, U3, L, U, Fw],
+            (43, vec![Fw3, L3, U3, L, U, Fw]),
         );
         algos.insert(
             [0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 1, 0],
-            vec![Fw, R, U, R3, U3, Fw3],
+            (44, vec![Fw, R, U, R3, U3, Fw3]),
         );
         algos.insert(
             [0, 0, 0, 0, 1, 0, 1, 0, 1, 0, 1, 0],
-            vec![F, R, U, R3, U3, F3],
+            (45, vec![F, R, U, R3, U3, F3]),
         );
         algos.insert(
             [1, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0],
-            vec![R3, U3, R3, F, R, F3, U, R],
+            (46, vec![R3, U3, R3, F, R, F3, U, R]),
         );
         algos.insert(
             [1, 0, 1, 0, 1, 1, 0, 1, 0, 1, 0, 0],
-            vec![F3, L3, U3, L, U, L3, U3, L, U, F],
+            (47, vec![F3, L3, U3, L, U, L3, U3, L, U, F]),
         );
         algos.insert(
             [0, 1, 0, 1, 1, 0, 1, 0, 1, 0, 0, 1],
-            vec![F, R, U, R3, U3, R, U, R3, U3, F3],
+            (48, vec![F, R, U, R3, U3, R, U, R3, U3, F3]),
         );
         algos.insert(
             [0, 0, 0, 1, 1, 0, 1, 1, 1, 0, 0, 1],
-            vec![R3, F, R3, F3, R2, U2, y, R3, F, R, F3, y3],
+            (49, vec![R3, F, R3, F3, R2, U2, y, R3, F, R, F3, y3]),
         );
         algos.insert(
             [1, 1, 1, 0, 1, 1, 0, 0, 0, 1, 0, 0],
-            vec![R3, F, R2, B3, R2, F3, R2, B, R3],
+            (50, vec![R3, F, R2, B3, R2, F3, R2, B, R3]),
         );
-        // 51
         algos.insert(
             [0, 0, 0, 1, 1, 0, 1, 0, 1, 0, 1, 1],
-            vec![Fw, R, U, R3, U3, R, U, R3, U3, Fw3],
+            (51, vec![Fw, R, U, R3, U3, R, U, R3, U3, Fw3]),
         );
         algos.insert(
             [1, 1, 1, 0, 0, 1, 0, 1, 0, 1, 0, 0],
-            vec![R, U, R3, U, R, Dw3, R, U3, R3, F3, Dw],
+            (52, vec![R, U, R3, U, R, Dw3, R, U3, R3, F3, Dw]),
         );
         algos.insert(
             [1, 0, 1, 0, 0, 0, 1, 1, 1, 0, 1, 0],
-            vec![Rw3, U3, R, U3, R3, U, R, U3, R3, U2, Rw],
+            (53, vec![Rw3, U3, R, U3, R3, U, R, U3, R3, U2, Rw]),
         );
         algos.insert(
             [1, 0, 1, 0, 1, 0, 1, 1, 1, 0, 0, 0],
-            vec![Rw, U, R3, U, R, U3, R3, U, R, U2, Rw3],
+            (54, vec![Rw, U, R3, U, R, U3, R3, U, R, U2, Rw3]),
         );
         algos.insert(
-            [0, 0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 1],
-            vec![R, U2, R2, U3, R, U3, R3, U2, F, R, F3],
+            [1, 1, 1, 0, 0, 0, 1, 1, 1, 0, 0, 0],
+            (55, vec![R, U2, R2, U3, R, U3, R3, U2, F, R, F3]),
         );
         algos.insert(
-            [1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0],
-            vec![Fw, R, U, R3, U3, Fw3, F, R, U, R3, U3, R, U, R3, U3, F3],
+            [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1],
+            (56, vec![Fw, R, U, R3, U3, Fw3, F, R, U, R3, U3, R, U, R3, U3, F3]),
         );
         algos.insert(
             [0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0],
-            vec![R, U, R3, U3, M3, U, R, U3, Rw3],
+            (57, vec![R, U, R3, U3, M3, U, R, U3, Rw3]),
         );
         Self {
             cube,
             algos,
+            case: None,
         }
     }
 
+    /// The OLL number of the case applied by `solve`, 0 if the last layer was already oriented.
+    pub fn case(&self) -> Option<u8> {
+        self.case
+    }
+
+    /// The algorithm of the OLL `number`.
+    pub(crate) fn algorithm(&self, number: u8) -> Option<Vec<Move>> {
+        self.algos
+            .values()
+            .find(|(n, _)| *n == number)
+            .map(|(_, algo)| algo.clone())
+    }
+
     /// Recognise which is Cube's OLL case.
     fn recognise(&self) -> [u8; 12] {
         let mut idx = [0u8; 12];
@@ -307,39 +320,38 @@ impl OLLSolver {
         idx
     }
 
-    /// Solve the OLL. Returns an Formula.
-    pub fn solve(&mut self) -> Vec<Move> {
-        let mut result = Vec::new();
+    /// Find the OLL case, returns the pre-AUF (number of U turns), the OLL number and the algorithm.
+    /// The OLL number is 0 if the last layer is already oriented.
+    pub(crate) fn find_case(&mut self) -> Result<(usize, u8, Vec<Move>), Error> {
+        check_cube_centers("OLL", &self.cube)?;
+        check_solved("OLL", &[("F2L", F2LSolver { cube: self.cube }.is_solved())])?;
+        if self.is_solved() {
+            return Ok((0, 0, Vec::new()));
+        }
+        let cube = self.cube;
         for i in 0..4 {
-            let mut put = match i {
-                1 => vec![Move::U],
-                2 => vec![Move::U2],
-                3 => vec![Move::U3],
-                _ => Vec::new(),
-            };
-            self.cube = self.cube.apply_moves(&put);
+            self.cube = cube.apply_moves(&auf_moves(i));
             let case = self.recognise();
-            let algo = self.algos.get(&case);
-            // println!("I: {} Case: {:?}, algo: {:?}", i, case, algo);
-            if algo.is_some() {
-                let algo = algo.expect("Algo");
+            if let Some((number, algo)) = self.algos.get(&case) {
                 self.cube = self.cube.apply_moves(algo);
-                // println!("Case: {:?}, algo: {:?}", case, algo);
                 if self.is_solved() {
-                    result.append(&mut put);
-                    result.append(&mut self.algos[&case].clone());
-                    break;
+                    self.cube = cube;
+                    return Ok((i, *number, algo.clone()));
                 }
             }
-            let put_r = match i {
-                3 => vec![Move::U],
-                2 => vec![Move::U2],
-                1 => vec![Move::U3],
-                _ => Vec::new(),
-            };
-            self.cube = self.cube.apply_moves(&put_r);
         }
-        result
+        self.cube = cube;
+        Err(stage_error("OLL", "unrecognised OLL case"))
+    }
+
+    /// Solve the OLL. Returns an Formula.
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        let (i, number, mut algo) = self.find_case()?;
+        let mut result = auf_moves(i);
+        result.append(&mut algo);
+        self.cube = self.cube.apply_moves(&result);
+        self.case = Some(number);
+        Ok(result)
     }
 
     /// Check if Cube is solved.
@@ -377,15 +389,32 @@ mod tests {
         let moves = vec![R, F3, B2, D3, F2, L, U2, L2, R2, L3, R, U2, F2, B2, R, B3, U, F2, D2, U2, B];
         let cc = cc.apply_moves(&moves);
         let mut cross = CrossSolver { cube: cc };
-        let _c = cross.solve();
+        let _c = cross.solve().unwrap();
         println!("{:?}", _c);
         let cc = cross.cube.clone();
         let mut f2l = F2LSolver { cube: cc };
-        let _f = f2l.solve();
+        let _f = f2l.solve().unwrap();
         println!("{:?}", _f);
         let cc = f2l.cube.clone();
         let mut oll = OLLSolver::new(cc);
-        let _o = oll.solve();
+        let _o = oll.solve().unwrap();
         println!("{:?}", _o);
     }
+
+    #[test]
+    fn test_oll_55_56() {
+        // The inverses of the algorithms of OLL 55 and 56.
+        let setups = [
+            (55, vec![F, R3, F3, U2, R, U, R3, U, R2, U2, R3]),
+            (56, vec![F, U, R, U3, R3, U, R, U3, R3, F3, Fw, U, R, U3, R3, Fw3]),
+        ];
+        for (number, setup) in setups {
+            let cc = CubieCube::default().apply_moves(&setup);
+            for auf in [N, U, U2, U3] {
+                let mut oll = OLLSolver::new(cc.apply_move(auf));
+                assert!(oll.solve().is_ok(), "OLL {}", number);
+                assert_eq!(oll.case(), Some(number));
+            }
+        }
+    }
 }
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
-use super::{corner_to_face, edge_to_face};
+use super::{corner_to_face, edge_to_face, F2LSolver, OLLSolver};
 use crate::cubie::CubieCube;
+use crate::error::Error;
 use crate::facelet::Color;
 use crate::moves::Move::{self, *};
+use crate::solver::{auf_moves, check_cube_centers, check_solved, stage_error};
 
 /// CrossSolver for solve CFOP's PLL. MUST HAVE SOLVED CROSS & F2L & OLL!!
 /// # Example
@@ -22,27 +24,28 @@ use crate::moves::Move::{self, *};
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver{cube: cc};
 ///     assert!(!cross.is_solved());
-///     let solution = cross.solve();
+///     let solution = cross.solve().unwrap();
 ///     assert!(cross.is_solved());
 ///     println!("Cross Solution: {:?}", solution);
 ///     let mut f2l = F2LSolver{cube: cross.cube};
 ///     assert!(!f2l.is_solved());
-///     let solution = f2l.solve();
+///     let solution = f2l.solve().unwrap();
 ///     assert!(f2l.is_solved());
 ///     println!("F2L Solution: {:?}", solution);
 ///     let mut oll = OLLSolver::new(f2l.cube);
-///     let solution = oll.solve();
+///     let solution = oll.solve().unwrap();
 ///     assert!(oll.is_solved());
 ///     println!("OLL Solution: {:?}", solution);
 ///     let mut pll = PLLSolver::new(oll.cube);
-///     let solution = pll.solve();
+///     let solution = pll.solve().unwrap();
 ///     assert!(pll.is_solved());
-///     println!("PLL Solution: {:?}", solution);
+///     println!("PLL {:?} Solution: {:?}", pll.case(), solution);
 /// }
 /// ```
 pub struct PLLSolver<'a> {
     pub cube: CubieCube,
-    algos: HashMap<&'a str, Vec<Move>>,
+    algos: HashMap<&'a str, (&'a str, Vec<Move>)>,
+    case: Option<&'a str>,
 }
 
 impl<'a> PLLSolver<'a> {
@@ -51,97 +54,103 @@ impl<'a> PLLSolver<'a> {
         let mut algos = HashMap::new();
         algos.insert(
             "RRRFFFLLLBBB",
-            vec![],
+            ("skip", vec![]),
         );
-        // PLL1
         algos.insert(
             "FRFLFBRLLBBR",
-            vec![x3, R2, D2, R3, U3, R, D2, R3, U, R3, x],
+            ("Aa", vec![x3, R2, D2, R3, U3, R, D2, R3, U, R3, x]),
         );
-        algos.insert("LRBRFRFLLBBF", vec![x3, R, U3, R, D2, R3, U, R, D2, R2, x]);
+        algos.insert("LRBRFRFLLBBF", ("Ab", vec![x3, R, U3, R, D2, R3, U, R, D2, R2, x]));
         algos.insert(
             "BRFLFRFLBRBL",
-            vec![
+            ("E", vec![
                 R2, U, R3, U3, y, R, U, R3, U3, R, U, R3, U3, R, U, R3, y3, R, U3, R2,
-            ],
+            ]),
         );
         algos.insert(
             "FRBRBFLLLBFR",
-            vec![
+            ("F", vec![
                 R3, U3, F3, R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, U, R,
-            ],
+            ]),
         );
         algos.insert(
             "LBRFFLBRBRLF",
-            vec![R2, Uw, R3, U, R3, U3, R, Uw3, R2, F3, U, F],
+            ("Ga", vec![R2, Uw, R3, U, R3, U3, R, Uw3, R2, F3, U, F]),
         );
         algos.insert(
             "BLBRFFLBRFRL",
-            vec![L2, Uw3, L, U3, L, U, L3, Uw, L2, F, U3, F3],
+            ("Gc", vec![L2, Uw3, L, U3, L, U, L3, Uw, L2, F, U3, F3]),
         );
 
         algos.insert(
             "BLRFFBRBFLRL",
-            vec![F3, U3, F, R2, Uw, R3, U, R, U3, R, Uw3, R2],
+            ("Gb", vec![F3, U3, F, R2, Uw, R3, U, R, U3, R, Uw3, R2]),
         );
         algos.insert(
             "FBLBFFLRBRLR",
-            vec![F, U, F3, L2, Uw3, L, U3, L3, U, L3, Uw, L2],
+            ("Gd", vec![F, U, F3, L2, Uw3, L, U3, L3, U, L3, Uw, L2]),
         );
-        // H Perm
-        algos.insert("RLRFBFLRLBFB", vec![M2, U, M2, U2, M2, U, M2]);
+        algos.insert("RLRFBFLRLBFB", ("H", vec![M2, U, M2, U2, M2, U, M2]));
         algos.insert(
             "RRRFLLBFFLBB",
-            vec![L3, U3, L, F, L3, U3, L, U, L, F3, L2, U, L, U],
+            ("Ja", vec![L3, U3, L, F, L3, U3, L, U, L, F3, L2, U, L, U]),
         );
         algos.insert(
             "FFBRRFLLLBBR",
-            vec![R, U, R3, F3, R, U, R3, U3, R3, F, R2, U3, R3, U3],
+            ("Jb", vec![R, U, R3, F3, R, U, R3, U3, R3, F, R2, U3, R3, U3]),
         );
-        // todo
         algos.insert(
             "RRLBBFLLRFFB",
-            vec![z, D, R3, U, R2, D3, R, D, U3, R3, U, R2, D3, R, U3, R, z3],
+            ("Na", vec![z, D, R3, U, R2, D3, R, D, U3, R3, U, R2, D3, R, U3, R, z3]),
         );
         algos.insert(
             "LRRFBBRLLBFF",
-            vec![z, U3, R, D3, R2, U, R3, D, U3, R, D3, R2, U, R3, D, R3, z3],
+            ("Nb", vec![z, U3, R, D3, R2, U, R3, D, U3, R, D3, R2, U, R3, D, R3, z3]),
         );
-        //Ra
         algos.insert(
             "BRRFLFLFBRBL",
-            vec![L, U2, L3, U2, L, F3, L3, U3, L, U, L, F, L2],
+            ("Ra", vec![L, U2, L3, U2, L, F3, L3, U3, L, U, L, F, L2]),
         );
-        //Rb
         algos.insert(
             "BFRFRFLLBRBL",
-            vec![R3, U2, R, U2, R3, F, R, U, R3, U3, R3, F3, R2],
+            ("Rb", vec![R3, U2, R, U2, R3, F, R, U, R3, U3, R3, F3, R2]),
         );
-        //T
         algos.insert(
             "FLBRFFLRLBBR",
-            vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3],
+            ("T", vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3]),
         );
-        //Ua
-        algos.insert("RLRFRFLFLBBB", vec![R, U3, R, U, R, U, R, U3, R3, U3, R2]);
-        //Ub
-        algos.insert("RFRFLFLRLBBB", vec![R2, U, R, U, R3, U3, R3, U3, R3, U, R3]);
+        algos.insert("RLRFRFLFLBBB", ("Ua", vec![R, U3, R, U, R, U, R, U3, R3, U3, R2]));
+        algos.insert("RFRFLFLRLBBB", ("Ub", vec![R2, U, R, U, R3, U3, R3, U3, R3, U, R3]));
         algos.insert(
             "RBLBFFLLRFRB",
-            vec![R3, U, R3, U3, y, R3, F3, R2, U3, R3, U, R3, F, R, F, y3],
+            ("V", vec![R3, U, R3, U3, y, R3, F3, R2, U3, R3, U, R3, F, R, F, y3]),
         );
         algos.insert(
             "RRLBFFLBRFLB",
-            vec![F, R, U3, R3, U3, R, U, R3, F3, R, U, R3, U3, R3, F, R, F3],
+            ("Y", vec![F, R, U3, R3, U3, R, U, R3, F3, R, U, R3, U3, R3, F, R, F3]),
         );
-        algos.insert("RFRFRFLBLBLB", vec![M2, U, M2, U, M3, U2, M2, U2, M3, U2]);
+        algos.insert("RFRFRFLBLBLB", ("Z", vec![M2, U, M2, U, M3, U2, M2, U2, M3, U2]));
 
         Self {
             cube,
             algos,
+            case: None,
         }
     }
 
+    /// The name of the PLL case applied by `solve`, eg. "T", or "skip" if only AUF was needed.
+    pub fn case(&self) -> Option<&str> {
+        self.case
+    }
+
+    /// The algorithm of the PLL case `name`, eg. "T".
+    pub(crate) fn algorithm(&self, name: &str) -> Option<Vec<Move>> {
+        self.algos
+            .values()
+            .find(|(n, _)| *n == name)
+            .map(|(_, algo)| algo.clone())
+    }
+
     /// Recognise which is Cube's PLL case.
     fn recognise(&self) -> [Color; 12] {
         let mut idx = [Color::U; 12];
@@ -160,60 +169,48 @@ impl<'a> PLLSolver<'a> {
         idx
     }
 
-    /// Solve the PLL. Returns an Formula.
-    pub fn solve(&mut self) -> Vec<Move> {
-        let mut result = Vec::new();
+    /// Find the PLL case, returns the pre-AUF (number of U turns), the case name, the algorithm and the post-AUF.
+    pub(crate) fn find_case(&mut self) -> Result<(usize, &'a str, Vec<Move>, usize), Error> {
+        check_cube_centers("PLL", &self.cube)?;
+        check_solved(
+            "PLL",
+            &[
+                ("F2L", F2LSolver { cube: self.cube }.is_solved()),
+                ("OLL", OLLSolver::new(self.cube).is_solved()),
+            ],
+        )?;
+        let cube = self.cube;
         for i in 0..4 {
-            let mut put = match i {
-                1 => vec![Move::U],
-                2 => vec![Move::U2],
-                3 => vec![Move::U3],
-                _ => Vec::new(),
-            };
-            self.cube = self.cube.apply_moves(&put);
+            self.cube = cube.apply_moves(&auf_moves(i));
+            let put_cube = self.cube;
             let case = self.recognise();
             // LBRF
             for r in 0..4 {
                 let case: String = case.iter().map(|c|rotate_color(*c, r)).map(|c| format!("{:?}", c)).collect();
-                let algo = self.algos.get(case.as_str());
-                // println!("U: {i}, r: {r}, Case:{case}, algo: {:?}", algo);
-                if algo.is_some() {
-                    let algo = algo.expect("Algo");
-                    self.cube = self.cube.apply_moves(algo);
+                if let Some((name, algo)) = self.algos.get(case.as_str()) {
                     for j in 0..4 {
-                        let mut u_put = match j {
-                            1 => vec![Move::U],
-                            2 => vec![Move::U2],
-                            3 => vec![Move::U3],
-                            _ => Vec::new(),
-                        };
-                        self.cube = self.cube.apply_moves(&u_put);
+                        self.cube = put_cube.apply_moves(algo).apply_moves(&auf_moves(j));
                         if self.is_solved() {
-                            result.append(&mut put);
-                            result.append(&mut self.algos[case.as_str()].clone());
-                            result.append(&mut u_put);
-                            return result;
+                            self.cube = cube;
+                            return Ok((i, *name, algo.clone(), j));
                         }
-                        let u_put_r = match j {
-                            1 => vec![Move::U3],
-                            2 => vec![Move::U2],
-                            3 => vec![Move::U],
-                            _ => Vec::new(),
-                        };
-                        self.cube = self.cube.apply_moves(&u_put_r);
                     }
                 }
             }
-            let put_r = match i {
-                3 => vec![Move::U],
-                2 => vec![Move::U2],
-                1 => vec![Move::U3],
-                _ => Vec::new(),
-            };
-            self.cube = self.cube.apply_moves(&put_r);
         }
-        // println!("PLL: {:?}", result);
-        result
+        self.cube = cube;
+        Err(stage_error("PLL", "unrecognised PLL case"))
+    }
+
+    /// Solve the PLL. Returns an Formula.
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        let (i, name, mut algo, j) = self.find_case()?;
+        let mut result = auf_moves(i);
+        result.append(&mut algo);
+        result.append(&mut auf_moves(j));
+        self.cube = self.cube.apply_moves(&result);
+        self.case = Some(name);
+        Ok(result)
     }
 
     /// Check if Cube is solved.
@@ -282,25 +279,25 @@ mod tests {
         ];
         let cc = cc.apply_moves(&moves);
         let mut cross = CrossSolver { cube: cc };
-        let _c = cross.solve();
+        let _c = cross.solve().unwrap();
         if !cross.is_solved() {
             panic!("Cross Error! {:?} : {:?}", moves, _c);
         }
         let cc = cross.cube.clone();
         let mut f2l = F2LSolver { cube: cc };
-        let _f = f2l.solve();
+        let _f = f2l.solve().unwrap();
         let cc = f2l.cube.clone();
         if !f2l.is_solved() {
             panic!("F2L Error! {:?} : {:?}: {:?}", moves, _c, _f);
         }
         let mut oll = OLLSolver::new(cc);
-        let _o = oll.solve();
+        let _o = oll.solve().unwrap();
         if !oll.is_solved() {
             panic!("OLL Error! {:?} : {:?} : {:?} : {:?}", moves, _c, _f, _o);
         }
         let cc = oll.cube.clone();
         let mut pll = PLLSolver::new(cc);
-        let _p = pll.solve();
+        let _p = pll.solve().unwrap();
         if !pll.is_solved() {
             panic!(
                 "PLL Error! {:?} : {:?} : {:?} : {:?} : {:?}",
@@ -1,7 +1,9 @@
 use crate::cubie::Corner::{self, *};
-use crate::solver::lbl::get_put_move;
+use crate::solver::lbl::{check_steps, get_put_move};
+use crate::solver::stage_error;
 use crate::{
     cubie::CubieCube,
+    error::Error,
     moves::Move::{self, *},
 };
 
@@ -18,9 +20,9 @@ use crate::{
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver::new(cc, true);
-///     let _cs = cross.solve();
+///     let _cs = cross.solve().unwrap();
 ///     let mut bottom = BottomCornerSolver{cube: cross.cube};
-///     let _bs = bottom.solve();
+///     let _bs = bottom.solve().unwrap();
 ///     assert!(bottom.is_solved());
 ///     println!("Scramble: {:?}\nSolution: {:?}, {:?}", moves, _cs, _bs);
 /// }
@@ -55,7 +57,8 @@ impl BottomCornerSolver {
         d_corners_sort
     }
 
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("Bottom corners", &self.cube, 1)?;
         let mut solution = Vec::new();
         let mut d_corners_sort = self.get_sorted_corners();
         'corners: while d_corners_sort.len() > 0 {
@@ -144,7 +147,10 @@ impl BottomCornerSolver {
             }
             d_corners_sort = self.get_sorted_corners();
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("Bottom corners", "no solution found"));
+        }
+        Ok(solution)
     }
 
     /// Check if the bottom layer's corner is solved.
@@ -190,9 +196,9 @@ mod tests {
         let moves = Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let mut cross = CrossSolver::new(cc, true);
-        let _cs = cross.solve();
+        let _cs = cross.solve().unwrap();
         let mut bottom = BottomCornerSolver { cube: cross.cube };
-        let _bs = bottom.solve();
+        let _bs = bottom.solve().unwrap();
         assert!(bottom.is_solved());
         // let _bs = optimise_moves(&_bs);
         println!("Scramble: {:?}\nSolution: {:?}, {:?}", moves, _cs, _bs);
@@ -1,7 +1,9 @@
 use crate::cubie::Corner::{self, *};
-use crate::solver::lbl::get_put_move;
+use crate::solver::lbl::{check_steps, get_put_move};
+use crate::solver::stage_error;
 use crate::{
     cubie::CubieCube,
+    error::Error,
     moves::Move::{self, *},
 };
 
@@ -21,17 +23,17 @@ use crate::{
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver::new(cc, true);
-///     let _cs = cross.solve();
+///     let _cs = cross.solve().unwrap();
 ///     let mut bottom = BottomCornerSolver { cube: cross.cube };
-///     let _bs = bottom.solve();
+///     let _bs = bottom.solve().unwrap();
 ///     // let _bs = optimise_moves(&_bs);
 ///     let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-///     let _ms = middle.solve();
+///     let _ms = middle.solve().unwrap();
 ///     // let _ms = optimise_moves(&_ms);
 ///     let mut eoll = EOLLSolver { cube: middle.cube };
-///     let _eos = eoll.solve();
+///     let _eos = eoll.solve().unwrap();
 ///     let mut coll = COLLSolver { cube: eoll.cube };
-///     let _cos = coll.solve();
+///     let _cos = coll.solve().unwrap();
 ///     assert!(coll.is_solved());
 ///     println!(
 ///         "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}, {:?}",
@@ -45,11 +47,12 @@ pub struct COLLSolver {
 
 impl COLLSolver {
     /// Solve the corner orientation of last layer corners(URF, UFL, ULB, UBR).
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("COLL", &self.cube, 4)?;
         let mut solution = Vec::new();
         match self.recognise() {
             0 => {
-                return solution;
+                return Ok(solution);
             }
             3 => {
                 for i in 0..4 {
@@ -62,7 +65,7 @@ impl COLLSolver {
                         if self.is_solved() {
                             solution.append(&mut u_put);
                             solution.append(&mut _s);
-                            return solution;
+                            return Ok(solution);
                         }
                         self.cube = _cube;
                     }
@@ -79,15 +82,18 @@ impl COLLSolver {
                     if self.recognise() == 3 {
                         solution.append(&mut u_put);
                         solution.append(&mut _s);
-                        let mut _s2 = self.solve();
+                        let mut _s2 = self.solve()?;
                         solution.append(&mut _s2);
-                        return solution;
+                        return Ok(solution);
                     }
                     self.cube = u_cube;
                 }
             }
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("COLL", "no solution found"));
+        }
+        Ok(solution)
     }
 
     fn recognise(&self) -> u8 {
@@ -132,17 +138,17 @@ mod tests {
         let moves = Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let mut cross = CrossSolver::new(cc, true);
-        let _cs = cross.solve();
+        let _cs = cross.solve().unwrap();
         let mut bottom = BottomCornerSolver { cube: cross.cube };
-        let _bs = bottom.solve();
+        let _bs = bottom.solve().unwrap();
         // let _bs = optimise_moves(&_bs);
         let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-        let _ms = middle.solve();
+        let _ms = middle.solve().unwrap();
         // let _ms = optimise_moves(&_ms);
         let mut eoll = EOLLSolver { cube: middle.cube };
-        let _eos = eoll.solve();
+        let _eos = eoll.solve().unwrap();
         let mut coll = COLLSolver { cube: eoll.cube };
-        let _cos = coll.solve();
+        let _cos = coll.solve().unwrap();
         assert!(coll.is_solved());
         println!(
             "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}, {:?}",
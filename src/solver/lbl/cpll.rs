@@ -1,7 +1,9 @@
 use crate::cubie::Corner::{self, *};
-use crate::solver::lbl::get_put_move;
+use crate::solver::lbl::{check_steps, get_put_move};
+use crate::solver::stage_error;
 use crate::{
     cubie::CubieCube,
+    error::Error,
     moves::Move::{self, *},
 };
 
@@ -22,20 +24,20 @@ use crate::{
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver::new(cc, true);
-///     let _cs = cross.solve();
+///     let _cs = cross.solve().unwrap();
 ///     let mut bottom = BottomCornerSolver { cube: cross.cube };
-///     let _bs = bottom.solve();
+///     let _bs = bottom.solve().unwrap();
 ///     // let _bs = optimise_moves(&_bs);
 ///     let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-///     let _ms = middle.solve();
+///     let _ms = middle.solve().unwrap();
 ///     // let _ms = optimise_moves(&_ms);
 ///     let mut eoll = EOLLSolver { cube: middle.cube };
-///     let _eos = eoll.solve();
+///     let _eos = eoll.solve().unwrap();
 ///     let mut coll = COLLSolver { cube: eoll.cube };
-///     let _cos = coll.solve();
+///     let _cos = coll.solve().unwrap();
 ///     assert!(coll.is_solved());
 ///     let mut cpll = CPLLSolver { cube: coll.cube };
-///     let _cps = cpll.solve();
+///     let _cps = cpll.solve().unwrap();
 ///     assert!(cpll.is_solved());
 ///     println!(
 ///         "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}, {:?}, {:?}",
@@ -49,14 +51,15 @@ pub struct CPLLSolver {
 
 impl CPLLSolver {
     /// Solve the corner permutation of last layer corners(URF, UFL, ULB, UBR).
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("CPLL", &self.cube, 5)?;
         let mut solution = Vec::new();
         for i in 0..4 {
             let u_cube = self.cube;
             let u_put = get_put_move(i, U);
             self.cube = self.cube.apply_moves(&u_put);
             if self.is_solved() {
-                return u_put;
+                return Ok(u_put);
             }
             self.cube = u_cube;
         }
@@ -75,7 +78,7 @@ impl CPLLSolver {
                     solution.append(&mut u_put);
                     solution.append(&mut _s);
                     solution.append(&mut u2_put);
-                    return solution;
+                    return Ok(solution);
                 }
                 self.cube = u2_cube;
             }
@@ -95,7 +98,7 @@ impl CPLLSolver {
                         solution.append(&mut uu_put);
                         solution.append(&mut _s2);
                         solution.append(&mut u2_put);
-                        return solution;
+                        return Ok(solution);
                     }
                     self.cube = u2_cube;
                 }
@@ -103,7 +106,10 @@ impl CPLLSolver {
             }
             self.cube = u_cube;
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("CPLL", "no solution found"));
+        }
+        Ok(solution)
     }
 
     /// Check if the last layer's corner permutation is solved.
@@ -149,20 +155,20 @@ mod tests {
         let moves =Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let mut cross = CrossSolver::new(cc, true);
-        let _cs = cross.solve();
+        let _cs = cross.solve().unwrap();
         let mut bottom = BottomCornerSolver { cube: cross.cube };
-        let _bs = bottom.solve();
+        let _bs = bottom.solve().unwrap();
         // let _bs = optimise_moves(&_bs);
         let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-        let _ms = middle.solve();
+        let _ms = middle.solve().unwrap();
         // let _ms = optimise_moves(&_ms);
         let mut eoll = EOLLSolver { cube: middle.cube };
-        let _eos = eoll.solve();
+        let _eos = eoll.solve().unwrap();
         let mut coll = COLLSolver { cube: eoll.cube };
-        let _cos = coll.solve();
+        let _cos = coll.solve().unwrap();
         assert!(coll.is_solved());
         let mut cpll = CPLLSolver { cube: coll.cube };
-        let _cps = cpll.solve();
+        let _cps = cpll.solve().unwrap();
         assert!(cpll.is_solved());
         println!(
             "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}, {:?}, {:?}",
@@ -5,8 +5,12 @@ use crate::{
         CubieCube,
         Edge::{self, *},
     },
+    error::Error,
     moves::Move::{self, *},
-    solver::lbl::{daisy::DaisySolver, get_put_move},
+    solver::{
+        lbl::{check_steps, daisy::DaisySolver, get_put_move},
+        stage_error,
+    },
 };
 
 /// CrossSolver for LBL(Layer by Layer) method, i.e, solve bottom cross(four edges: DR, DF, DL, DB).
@@ -21,7 +25,7 @@ use crate::{
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut solver = CrossSolver::new(cc, true);
-///     let solution = solver.solve();
+///     let solution = solver.solve().unwrap();
 ///     assert!(solver.is_solved());
 ///     println!("Scramble: {:?}\nSolution: {:?}", moves, solution);
 /// }
@@ -40,12 +44,12 @@ impl CrossSolver {
         }
     }
 
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("Cross", &self.cube, 0)?;
         let mut solution = Vec::new();
         if self.daisy {
             let mut daisy = DaisySolver { cube: self.cube };
-            let mut daisy_solution = daisy.solve();
-            assert!(daisy.is_solved());
+            let mut daisy_solution = daisy.solve()?;
             self.cube = daisy.cube;
             solution.append(&mut daisy_solution);
             for edge in [DR, DF, DL, DB] {
@@ -67,7 +71,10 @@ impl CrossSolver {
                 }
             }
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("Cross", "no solution found"));
+        }
+        Ok(solution)
     }
 
     /// Check if the cross is solved.
@@ -94,7 +101,7 @@ mod tests {
         let moves =Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let mut cross = CrossSolver::new(cc, true);
-        let _cs = cross.solve();
+        let _cs = cross.solve().unwrap();
         assert!(cross.is_solved());
     }
 }
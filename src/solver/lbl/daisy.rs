@@ -1,9 +1,10 @@
 use std::str::FromStr;
 
-use super::{get_move_face, get_put_move};
+use super::{check_steps, get_move_face, get_put_move};
 use crate::cubie::Edge::{self, *};
 use crate::error::Error;
 use crate::facelet::Color;
+use crate::solver::stage_error;
 use crate::{
     cubie::CubieCube,
     moves::Move::{self, *},
@@ -22,7 +23,7 @@ use crate::{
 ///     let cc = cc.apply_formula(&moves);
 ///     let cc2 = cc.clone();
 ///     let mut daisy = DaisySolver{cube: cc};
-///     let _cs = daisy.solve();
+///     let _cs = daisy.solve().unwrap();
 ///     assert!(daisy.is_solved());
 ///     let cc2 = cc2.apply_moves(&_cs);
 ///     assert_eq!(cc2, daisy.cube);
@@ -34,7 +35,8 @@ pub struct DaisySolver {
 }
 
 impl DaisySolver {
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("Daisy", &self.cube, 0)?;
         let mut solution = Vec::new();
         let cc = CubieCube::default();
         let mut d_edges = Vec::new();
@@ -154,7 +156,10 @@ impl DaisySolver {
                 }
             }
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("Daisy", "no solution found"));
+        }
+        Ok(solution)
     }
 
     pub fn is_solved(&self) -> bool {
@@ -243,7 +248,7 @@ mod tests {
         let cc = cc.apply_formula(&moves);
         let cc2 = cc.clone();
         let mut daisy = DaisySolver { cube: cc };
-        let _cs = daisy.solve();
+        let _cs = daisy.solve().unwrap();
         assert!(daisy.is_solved());
         let cc2 = cc2.apply_moves(&_cs);
         assert_eq!(cc2, daisy.cube);
@@ -1,7 +1,9 @@
 use crate::cubie::Edge::{self, *};
-use crate::solver::lbl::get_put_move;
+use crate::solver::lbl::{check_steps, get_put_move};
+use crate::solver::stage_error;
 use crate::{
     cubie::CubieCube,
+    error::Error,
     moves::Move::{self, *},
 };
 
@@ -20,17 +22,17 @@ use crate::{
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver::new(cc, true);
-///     let _cs = cross.solve();
+///     let _cs = cross.solve().unwrap();
 ///     let mut bottom = BottomCornerSolver { cube: cross.cube };
-///     let _bs = bottom.solve();
+///     let _bs = bottom.solve().unwrap();
 ///     assert!(bottom.is_solved());
 ///     // let _bs = optimise_moves(&_bs);
 ///     let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-///     let _ms = middle.solve();
+///     let _ms = middle.solve().unwrap();
 ///     assert!(middle.is_solved());
 ///     // let _ms = optimise_moves(&_ms);
 ///     let mut eoll = EOLLSolver { cube: middle.cube };
-///     let _eos = eoll.solve();
+///     let _eos = eoll.solve().unwrap();
 ///     assert!(eoll.is_solved());
 ///     println!(
 ///         "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}",
@@ -44,15 +46,16 @@ pub struct EOLLSolver {
 
 impl EOLLSolver {
     /// Solve the edge orientation of last layer edges(UR, UF, UL, UB).
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("EOLL", &self.cube, 3)?;
         let mut solution = Vec::new();
         let case = self.recognise();
         if case == 0 {
-            return solution;
+            return Ok(solution);
         } else if case == 4 {
             let solution = vec![F, R, U, R3, U3, R, U, R3, U3, F3, U3, F, R, U, R3, U3, F3];
             self.cube = self.cube.apply_moves(&solution);
-            return solution;
+            return Ok(solution);
         } else {
             for i in 0..4 {
                 let u_cube = self.cube;
@@ -67,14 +70,17 @@ impl EOLLSolver {
                     if self.is_solved() {
                         solution.append(&mut u_put);
                         solution.append(&mut _s);
-                        return solution;
+                        return Ok(solution);
                     }
                     self.cube = s_cube;
                 }
                 self.cube = u_cube;
             }
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("EOLL", "no solution found"));
+        }
+        Ok(solution)
     }
 
     fn recognise(&self) -> u8 {
@@ -117,17 +123,17 @@ mod tests {
         let moves =Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let mut cross = CrossSolver::new(cc, true);
-        let _cs = cross.solve();
+        let _cs = cross.solve().unwrap();
         let mut bottom = BottomCornerSolver { cube: cross.cube };
-        let _bs = bottom.solve();
+        let _bs = bottom.solve().unwrap();
         assert!(bottom.is_solved());
         // let _bs = optimise_moves(&_bs);
         let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-        let _ms = middle.solve();
+        let _ms = middle.solve().unwrap();
         assert!(middle.is_solved());
         // let _ms = optimise_moves(&_ms);
         let mut eoll = EOLLSolver { cube: middle.cube };
-        let _eos = eoll.solve();
+        let _eos = eoll.solve().unwrap();
         assert!(eoll.is_solved());
         println!(
             "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}",
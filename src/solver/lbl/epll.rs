@@ -1,7 +1,9 @@
 use crate::cubie::Edge::{self, *};
-use crate::solver::lbl::get_put_move;
+use crate::solver::lbl::{check_steps, get_put_move};
+use crate::solver::stage_error;
 use crate::{
     cubie::CubieCube,
+    error::Error,
     moves::Move::{self, *},
 };
 
@@ -23,22 +25,22 @@ use crate::{
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver::new(cc, true);
-///     let _cs = cross.solve();
+///     let _cs = cross.solve().unwrap();
 ///     let mut bottom = BottomCornerSolver { cube: cross.cube };
-///     let _bs = bottom.solve();
+///     let _bs = bottom.solve().unwrap();
 ///     // let _bs = optimise_moves(&_bs);
 ///     let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-///     let _ms = middle.solve();
+///     let _ms = middle.solve().unwrap();
 ///     // let _ms = optimise_moves(&_ms);
 ///     let mut eoll = EOLLSolver { cube: middle.cube };
-///     let _eos = eoll.solve();
+///     let _eos = eoll.solve().unwrap();
 ///     let mut coll = COLLSolver { cube: eoll.cube };
-///     let _cos = coll.solve();
+///     let _cos = coll.solve().unwrap();
 ///     let mut cpll = CPLLSolver { cube: coll.cube };
-///     let _cps = cpll.solve();
+///     let _cps = cpll.solve().unwrap();
 ///     assert!(cpll.is_solved());
 ///     let mut epll = EPLLSolver { cube: cpll.cube };
-///     let _eps = epll.solve();
+///     let _eps = epll.solve().unwrap();
 ///     assert!(epll.is_solved());
 ///     println!(
 ///         "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}",
@@ -52,13 +54,14 @@ pub struct EPLLSolver {
 
 impl EPLLSolver {
     /// Solve the edge permutation of last layer edges(UR, UF, UL, UB).
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("EPLL", &self.cube, 6)?;
         for i in 0..4 {
             let u_put = get_put_move(i, U);
             let u_cube = self.cube;
             self.cube = self.cube.apply_moves(&u_put);
             if self.is_solved() {
-                return u_put;
+                return Ok(u_put);
             }
             self.cube = u_cube;
         }
@@ -81,7 +84,7 @@ impl EPLLSolver {
                         solution.append(&mut u_put);
                         solution.append(&mut _s);
                         solution.append(&mut u2_put);
-                        return solution;
+                        return Ok(solution);
                     } else {
                         for ii in 0..4 {
                             let uu_cube = self.cube;
@@ -103,7 +106,7 @@ impl EPLLSolver {
                                         solution.append(&mut uu_put);
                                         solution.append(&mut _s2);
                                         solution.append(&mut uu2_put);
-                                        return solution;
+                                        return Ok(solution);
                                     }
                                     self.cube = uu2_cube;
                                 }
@@ -118,7 +121,10 @@ impl EPLLSolver {
             }
             self.cube = u_cube;
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("EPLL", "no solution found"));
+        }
+        Ok(solution)
     }
 
     /// Check if the last layer's edge permutation is solved.
@@ -164,22 +170,22 @@ mod tests {
         let moves =Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let mut cross = CrossSolver::new(cc, true);
-        let _cs = cross.solve();
+        let _cs = cross.solve().unwrap();
         let mut bottom = BottomCornerSolver { cube: cross.cube };
-        let _bs = bottom.solve();
+        let _bs = bottom.solve().unwrap();
         // let _bs = optimise_moves(&_bs);
         let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-        let _ms = middle.solve();
+        let _ms = middle.solve().unwrap();
         // let _ms = optimise_moves(&_ms);
         let mut eoll = EOLLSolver { cube: middle.cube };
-        let _eos = eoll.solve();
+        let _eos = eoll.solve().unwrap();
         let mut coll = COLLSolver { cube: eoll.cube };
-        let _cos = coll.solve();
+        let _cos = coll.solve().unwrap();
         let mut cpll = CPLLSolver { cube: coll.cube };
-        let _cps = cpll.solve();
+        let _cps = cpll.solve().unwrap();
         assert!(cpll.is_solved());
         let mut epll = EPLLSolver { cube: cpll.cube };
-        let _eps = epll.solve();
+        let _eps = epll.solve().unwrap();
         assert!(epll.is_solved());
         println!(
             "Scramble: {:?}\nSolution: {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}",
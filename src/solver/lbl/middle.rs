@@ -1,7 +1,9 @@
 use crate::cubie::Edge::{self, *};
-use crate::solver::lbl::get_put_move;
+use crate::solver::lbl::{check_steps, get_put_move};
+use crate::solver::stage_error;
 use crate::{
     cubie::CubieCube,
+    error::Error,
     moves::Move::{self, *},
 };
 
@@ -19,11 +21,11 @@ use crate::{
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
 ///     let mut cross = CrossSolver::new(cc, true);
-///     let _cs = cross.solve();
+///     let _cs = cross.solve().unwrap();
 ///     let mut bottom = BottomCornerSolver{cube: cross.cube};
-///     let _bs = bottom.solve();
+///     let _bs = bottom.solve().unwrap();
 ///     let mut middle = MiddleEdgeSolver{cube: bottom.cube};
-///     let _ms = middle.solve();
+///     let _ms = middle.solve().unwrap();
 ///     assert!(middle.is_solved());
 ///     println!("Scramble: {:?}\nSolution: {:?}, {:?}, {:?}", moves, _cs, _bs, _ms);
 /// }
@@ -54,7 +56,8 @@ impl MiddleEdgeSolver {
         m_edges_sort
     }
 
-    pub fn solve(&mut self) -> Vec<Move> {
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("Middle edges", &self.cube, 2)?;
         let mut solution = Vec::new();
         let mut m_edges_sort = self.get_sorted_edges();
         'edges: while m_edges_sort.len() > 0 {
@@ -114,7 +117,10 @@ impl MiddleEdgeSolver {
             }
             m_edges_sort = self.get_sorted_edges();
         }
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("Middle edges", "no solution found"));
+        }
+        Ok(solution)
     }
 
     /// Check if the middle layer's edge is solved.
@@ -160,12 +166,12 @@ mod tests {
         let moves = Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let mut cross = CrossSolver::new(cc, true);
-        let _cs = cross.solve();
+        let _cs = cross.solve().unwrap();
         let mut bottom = BottomCornerSolver { cube: cross.cube };
-        let _bs = bottom.solve();
+        let _bs = bottom.solve().unwrap();
         // let _bs = optimise_moves(&_bs);
         let mut middle = MiddleEdgeSolver { cube: bottom.cube };
-        let _ms = middle.solve();
+        let _ms = middle.solve().unwrap();
         assert!(middle.is_solved());
         // let _ms = optimise_moves(&_ms);
         println!(
@@ -33,8 +33,10 @@ pub mod epll;
 /// Module for MiddleEdgeSolver of LBL(Layer by Layer) method(step 3).
 pub mod middle;
 
+use crate::error::Error;
 use crate::facelet::Color;
 use crate::moves::Formula;
+use crate::solver::{check_cube_centers, check_solved, Solution, SolveReport, Solver};
 use crate::{cubie::CubieCube, moves::Move};
 
 pub use bottom::BottomCornerSolver;
@@ -51,14 +53,15 @@ pub use middle::MiddleEdgeSolver;
 /// ```rust
 /// use rcuber::cubie::CubieCube;
 /// use rcuber::moves::Formula;
+/// use rcuber::solver::Solver;
 /// use rcuber::solver::lbl::LBLSolver;
 ///
 /// fn main() {
 ///     let cc = CubieCube::default();
 ///     let moves = Formula::scramble();
 ///     let cc = cc.apply_formula(&moves);
-///     let mut solver = LBLSolver{cube: cc};
-///     let solution = solver.solve();
+///     let mut solver = LBLSolver::new(cc);
+///     let solution = solver.solve().unwrap();
 ///     assert!(solver.is_solved());
 ///     println!("Scramble: {:?}\nSolution: {:?}", moves, solution);
 /// }
@@ -67,52 +70,75 @@ pub struct LBLSolver {
     pub cube: CubieCube,
 }
 
-impl LBLSolver {
+impl Solver for LBLSolver {
+    fn new(cube: CubieCube) -> Self {
+        Self { cube }
+    }
+
     /// Solve the cube.
-    pub fn solve(&mut self) -> Vec<Move> {
-        let mut solution = Vec::new();
+    fn solve(&mut self) -> Result<Solution, Error> {
+        let solution = Formula::from(self.solve_report()?.solution());
+        Ok(Solution::from(solution.optimise()))
+    }
+
+    fn is_solved(&self) -> bool {
+        let cc = CubieCube::default();
+        self.cube == cc
+    }
+
+    fn name(&self) -> &'static str {
+        "LBL"
+    }
+}
+
+impl LBLSolver {
+    /// Solve the cube and report each layer step, the moves of the steps are not optimised.
+    pub fn solve_report(&mut self) -> Result<SolveReport, Error> {
+        let mut report = SolveReport::default();
         let mut cross = CrossSolver::new(self.cube, true);
-        let mut _cs = cross.solve();
-        assert!(cross.is_solved());
+        let _cs = cross.solve()?;
         self.cube = cross.cube;
-        solution.append(&mut _cs);
+        report.push("Cross".to_string(), _cs, None, self.cube);
         let mut bottom = BottomCornerSolver { cube: self.cube };
-        let mut _bs = bottom.solve();
-        assert!(bottom.is_solved());
+        let _bs = bottom.solve()?;
         self.cube = bottom.cube;
-        solution.append(&mut _bs);
+        report.push("Bottom corners".to_string(), _bs, None, self.cube);
         let mut middle = MiddleEdgeSolver { cube: self.cube };
-        let mut _ms = middle.solve();
-        assert!(middle.is_solved());
+        let _ms = middle.solve()?;
         self.cube = middle.cube;
-        solution.append(&mut _ms);
+        report.push("Middle edges".to_string(), _ms, None, self.cube);
         let mut eoll = EOLLSolver { cube: self.cube };
-        let mut _eos = eoll.solve();
-        assert!(eoll.is_solved());
+        let _eos = eoll.solve()?;
         self.cube = eoll.cube;
-        solution.append(&mut _eos);
+        report.push("EOLL".to_string(), _eos, None, self.cube);
         let mut coll = COLLSolver { cube: self.cube };
-        let mut _cos = coll.solve();
-        assert!(coll.is_solved());
+        let _cos = coll.solve()?;
         self.cube = coll.cube;
-        solution.append(&mut _cos);
+        report.push("COLL".to_string(), _cos, None, self.cube);
         let mut cpll = CPLLSolver { cube: self.cube };
-        let mut _cps = cpll.solve();
-        assert!(cpll.is_solved());
+        let _cps = cpll.solve()?;
         self.cube = cpll.cube;
-        solution.append(&mut _cps);
+        report.push("CPLL".to_string(), _cps, None, self.cube);
         let mut epll = EPLLSolver { cube: self.cube };
-        let mut _eps = epll.solve();
-        assert!(epll.is_solved());
+        let _eps = epll.solve()?;
         self.cube = epll.cube;
-        solution.append(&mut _eps);
-        Formula { moves: solution }.optimise().moves
+        report.push("EPLL".to_string(), _eps, None, self.cube);
+        Ok(report)
     }
+}
 
-    pub fn is_solved(&self) -> bool {
-        let cc = CubieCube::default();
-        self.cube == cc
-    }
+/// Check if the cube is valid and the LBL steps before the `step`th one are solved.
+pub(crate) fn check_steps(stage: &'static str, cube: &CubieCube, step: usize) -> Result<(), Error> {
+    check_cube_centers(stage, cube)?;
+    let steps = [
+        ("Cross", CrossSolver::new(*cube, true).is_solved()),
+        ("Bottom corners", BottomCornerSolver { cube: *cube }.is_solved()),
+        ("Middle edges", MiddleEdgeSolver { cube: *cube }.is_solved()),
+        ("EOLL", EOLLSolver { cube: *cube }.is_solved()),
+        ("COLL", COLLSolver { cube: *cube }.is_solved()),
+        ("CPLL", CPLLSolver { cube: *cube }.is_solved()),
+    ];
+    check_solved(stage, &steps[..step])
 }
 
 pub fn get_move_face(step: Move) -> Color {
@@ -134,7 +160,12 @@ pub fn get_put_move(i: usize, step: Move) -> Vec<Move> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{cubie::CubieCube, moves::Formula, solver::LBLSolver};
+    use crate::{
+        cubie::CubieCube,
+        error::Error,
+        moves::Formula,
+        solver::{lbl::EOLLSolver, LBLSolver, Solver},
+    };
 
     #[test]
     fn test_lbl() {
@@ -142,12 +173,45 @@ mod tests {
         let moves = Formula::scramble();
         let cc = cc.apply_formula(&moves);
         let cc2 = cc.clone();
-        let mut solver = LBLSolver { cube: cc };
-        let solution = solver.solve();
+        let mut solver = LBLSolver::new(cc);
+        let solution = solver.solve().unwrap();
         assert!(solver.is_solved());
 
-        let cc2 = cc2.apply_moves(&solution);
+        let cc2 = cc2.apply_moves(&solution.moves);
         assert_eq!(cc2, CubieCube::default());
         println!("Scramble: {:?}\nSolution: {:?}", moves, solution);
     }
+
+    #[test]
+    fn test_lbl_report() {
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        let mut solver = LBLSolver::new(cc);
+        let report = solver.solve_report().unwrap();
+        let names: Vec<&str> = report.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Cross", "Bottom corners", "Middle edges", "EOLL", "COLL", "CPLL", "EPLL"]
+        );
+        assert_eq!(cc.apply_moves(&report.solution().moves), CubieCube::default());
+    }
+
+    #[test]
+    fn test_lbl_stage_error() {
+        let mut cc = CubieCube::default();
+        cc.eo[0] = 1;
+        let mut solver = LBLSolver::new(cc);
+        assert!(matches!(
+            solver.solve(),
+            Err(Error::StageFailed { stage: "Cross", .. })
+        ));
+
+        let mut cc = CubieCube::default();
+        cc.ep.swap(8, 9);
+        cc.cp.swap(0, 1);
+        let mut eoll = EOLLSolver { cube: cc };
+        match eoll.solve() {
+            Err(e) => assert_eq!(e.to_string(), "EOLL failed: Middle edges is not solved"),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+    }
 }
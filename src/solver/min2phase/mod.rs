@@ -23,24 +23,25 @@ pub mod tables;
 /// Module for misc utils and tables.
 pub mod utils;
 
-use crate::moves::Formula;
+use crate::error::Error;
+use crate::solver::{Solution, Solver};
 use crate::{cubie::CubieCube, facelet::FaceCube};
-use solver::Solver;
 
 /// Min2PhaseSolver for solve a cube use min2phase method.
 /// # Example
 /// ```rust
 /// use rcuber::cubie::CubieCube;
 /// use rcuber::moves::Formula;
+/// use rcuber::solver::Solver;
 /// use rcuber::solver::min2phase::Min2PhaseSolver;
 ///
 /// fn main() {
 ///     let cc = CubieCube::default();
 ///     let formula = Formula::scramble();
 ///     let cc = cc.apply_formula(&formula);
-///     let mut solver = Min2PhaseSolver{cube: cc};
+///     let mut solver = Min2PhaseSolver::new(cc);
 ///     assert!(!solver.is_solved());
-///     let solution = solver.solve();
+///     let solution = solver.solve().unwrap();
 ///     assert!(solver.is_solved());
 ///     println!("Scramble: {:?}\nSolution: {:?}", formula, solution);
 /// }
@@ -52,24 +53,31 @@ pub struct Min2PhaseSolver {
     pub cube: CubieCube,
 }
 
-impl Min2PhaseSolver {
-    pub fn solve(&mut self) -> Formula {
-        let mut solver = Solver::default();
-        let s = solver
-            .solve(
-                FaceCube::try_from(&self.cube).unwrap().to_string().as_str(),
-                21,
-                1000000,
-                0,
-                0x0,
-            )
-            .unwrap();
+impl Solver for Min2PhaseSolver {
+    fn new(cube: CubieCube) -> Self {
+        Self { cube }
+    }
+
+    fn solve(&mut self) -> Result<Solution, Error> {
+        let mut solver = solver::Solver::default();
+        let s = solver.solve(
+            FaceCube::try_from(&self.cube)?.to_string().as_str(),
+            21,
+            1000000,
+            0,
+            0x0,
+        )?;
         self.cube = self.cube.apply_formula(&s);
-        s
+        Ok(Solution::from(s.optimise()))
     }
-    pub fn is_solved(&self) -> bool {
+
+    fn is_solved(&self) -> bool {
         self.cube == CubieCube::default()
     }
+
+    fn name(&self) -> &'static str {
+        "min2phase"
+    }
 }
 
 #[cfg(test)]
@@ -77,15 +85,16 @@ mod tests {
     use crate::cubie::CubieCube;
     use crate::moves::Formula;
     use crate::solver::min2phase::Min2PhaseSolver;
+    use crate::solver::Solver;
 
     #[test]
     fn test_solver() {
         let cc = CubieCube::default();
         let formula = Formula::scramble();
         let cc = cc.apply_formula(&formula);
-        let mut solver = Min2PhaseSolver { cube: cc };
+        let mut solver = Min2PhaseSolver::new(cc);
         assert!(!solver.is_solved());
-        let solution = solver.solve();
+        let solution = solver.solve().unwrap();
         assert!(solver.is_solved());
         println!("Scramble: {:?}\nSolution: {:?}", formula, solution);
     }
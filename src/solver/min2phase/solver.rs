@@ -530,27 +530,28 @@ impl Solver {
             return prun as u32 - self.max_dep2 as u32;
         }
 
-        let mut depth2 = self.max_dep2;
-        while depth2 >= prun as usize {
+        // Signed as in the original, the depth goes below 0 once a solution of depth 0 is found.
+        let mut depth2 = self.max_dep2 as i32;
+        while depth2 >= prun {
             let ret = self.phase2(
                 p2edge,
                 p2esym,
                 p2corn,
                 p2csym,
                 p2mid,
-                depth2,
+                depth2 as usize,
                 self.depth1,
                 10,
             );
             if ret < 0 {
                 break;
             }
-            depth2 -= ret as usize;
+            depth2 -= ret;
             self.sol_len = 0;
             self.solution = Solution::new();
             self.solution
                 .set_args(self.verbose, self.urf_idx, self.depth1);
-            for i in 0..self.depth1 + depth2 {
+            for i in 0..self.depth1 + depth2 as usize {
                 self.solution.append_sol_move(self.moves[i]);
             }
             for i in (0..self.pre_move_len).rev() {
@@ -559,9 +560,9 @@ impl Solver {
             self.sol_len = self.solution.length;
             depth2 -= 1;
         }
-        if depth2 != self.max_dep2 {
+        if depth2 != self.max_dep2 as i32 {
             //At least one solution has been found.
-            self.max_dep2 = min(MAX_DEPTH2, self.sol_len - self.length1 - 1);
+            self.max_dep2 = min(MAX_DEPTH2, self.sol_len.saturating_sub(self.length1 + 1));
             return match self.probe >= self.probe_min {
                 true => 0,
                 false => 1,
@@ -7,7 +7,236 @@ pub mod roux;
 /// Module for min2phase method.
 pub mod min2phase;
 
+use std::fmt;
+
 pub use cfop::CFOPSolver;
 pub use lbl::LBLSolver;
 pub use roux::RouxSolver;
 pub use min2phase::Min2PhaseSolver;
+
+use crate::cubie::{CubieCube, SOLVED_CUBIE_CUBE};
+use crate::error::Error;
+use crate::moves::{Formula, Metric, Move};
+use crate::predicates;
+
+/// Common interface of the method solvers (LBL, CFOP, Roux, min2phase).
+/// # Example
+/// ```rust
+/// use rcuber::cubie::CubieCube;
+/// use rcuber::moves::{Formula, Metric};
+/// use rcuber::solver::{CFOPSolver, LBLSolver, Min2PhaseSolver, RouxSolver, Solver};
+///
+/// fn main() {
+///     let cc = CubieCube::default();
+///     let moves = Formula::scramble();
+///     let cc = cc.apply_formula(&moves);
+///     let solvers: Vec<Box<dyn Solver>> = vec![
+///         Box::new(LBLSolver::new(cc)),
+///         Box::new(CFOPSolver::new(cc)),
+///         Box::new(RouxSolver::new(cc)),
+///         Box::new(Min2PhaseSolver::new(cc)),
+///     ];
+///     for mut solver in solvers {
+///         let solution = solver.solve().unwrap();
+///         assert!(solver.is_solved());
+///         assert_eq!(cc.apply_moves(&solution.moves), CubieCube::default());
+///         println!("{}: {} ({} HTM)", solver.name(), solution, solution.count(Metric::HTM));
+///     }
+/// }
+/// ```
+pub trait Solver {
+    /// Construct the solver for a cube.
+    fn new(cube: CubieCube) -> Self
+    where
+        Self: Sized;
+    /// Solve the cube, the solver's cube is solved after success.
+    fn solve(&mut self) -> Result<Solution, Error>;
+    /// Check if the solver's cube is solved.
+    fn is_solved(&self) -> bool;
+    /// Name of the method, eg. "CFOP".
+    fn name(&self) -> &'static str;
+}
+
+/// `Solution` is the result of a `Solver`, i.e, the moves to solve the cube.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solution {
+    pub moves: Vec<Move>,
+}
+
+impl Solution {
+    /// Number of moves of the solution.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Length of the solution in the `metric`, eg. `Metric::HTM`.
+    pub fn count(&self, metric: Metric) -> usize {
+        Formula::from(self.clone()).count(metric)
+    }
+
+    /// Check if the solution has no move, i.e, the cube was already solved.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s: String = self.moves.iter().map(|m| m.to_string() + " ").collect();
+        write!(f, "{}", s.trim())
+    }
+}
+
+impl From<Vec<Move>> for Solution {
+    fn from(moves: Vec<Move>) -> Self {
+        Self { moves }
+    }
+}
+
+impl From<Formula> for Solution {
+    fn from(formula: Formula) -> Self {
+        Self {
+            moves: formula.moves,
+        }
+    }
+}
+
+impl From<Solution> for Formula {
+    fn from(solution: Solution) -> Self {
+        Self {
+            moves: solution.moves,
+        }
+    }
+}
+
+/// A stage of a `SolveReport`, eg. "Cross", "F2L pair FR", "OLL 27", "PLL T", "CMLL pi_right_bar".
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StageReport {
+    /// Name of the stage, with the recognized case if any.
+    pub name: String,
+    /// Moves of the stage.
+    pub moves: Vec<Move>,
+    /// Recognized case id, eg. "27" for OLL 27, "T" for PLL T, "skip" if the stage was already solved.
+    pub case: Option<String>,
+    /// State of the cube after the stage.
+    pub cube: CubieCube,
+}
+
+/// `SolveReport` is the step by step result of a method solver (CFOP, Roux, LBL).
+/// # Example
+/// ```rust
+/// use rcuber::cubie::CubieCube;
+/// use rcuber::moves::Formula;
+/// use rcuber::solver::Solver;
+/// use rcuber::solver::cfop::CFOPSolver;
+///
+/// fn main() {
+///     let cc = CubieCube::default();
+///     let moves = Formula::scramble();
+///     let cc = cc.apply_formula(&moves);
+///     let mut solver = CFOPSolver::new(cc);
+///     let report = solver.solve_report().unwrap();
+///     assert_eq!(report.stages[0].name, "Cross");
+///     assert!(report.stages.last().unwrap().name.starts_with("PLL"));
+///     assert_eq!(cc.apply_moves(&report.solution().moves), CubieCube::default());
+///     println!("{}", report);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveReport {
+    pub stages: Vec<StageReport>,
+}
+
+impl SolveReport {
+    /// Append a stage to the report.
+    pub fn push(&mut self, name: String, moves: Vec<Move>, case: Option<String>, cube: CubieCube) {
+        self.stages.push(StageReport {
+            name,
+            moves,
+            case,
+            cube,
+        });
+    }
+
+    /// Join the moves of all stages.
+    pub fn solution(&self) -> Solution {
+        Solution::from(
+            self.stages
+                .iter()
+                .flat_map(|s| s.moves.clone())
+                .collect::<Vec<Move>>(),
+        )
+    }
+}
+
+impl fmt::Display for SolveReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for stage in &self.stages {
+            writeln!(f, "{}: {}", stage.name, Solution::from(stage.moves.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+/// The U turns of an AUF (Adjust U Face) of `n` quarter turns.
+pub(crate) fn auf_moves(n: usize) -> Vec<Move> {
+    match n % 4 {
+        1 => vec![Move::U],
+        2 => vec![Move::U2],
+        3 => vec![Move::U3],
+        _ => Vec::new(),
+    }
+}
+
+/// Build the error of a failed stage.
+pub(crate) fn stage_error(stage: &'static str, reason: &str) -> Error {
+    Error::StageFailed {
+        stage,
+        reason: reason.to_string(),
+    }
+}
+
+/// Check if the cube is a valid cubie representation before running a stage.
+pub(crate) fn check_cube(stage: &'static str, cube: &CubieCube) -> Result<(), Error> {
+    // The centers only move by rotations, a mirrored or scrambled arrangement is no cube.
+    if !predicates::orientations()
+        .iter()
+        .any(|(_, o)| SOLVED_CUBIE_CUBE.apply_moves(&o.rotation).center == cube.center)
+    {
+        return Err(stage_error(stage, "invalid cube, the centers are not in any orientation"));
+    }
+    match cube.verify() {
+        // `verify` ignores the centers: an odd center permutation (eg. after an M move) goes with
+        // different corner and edge parities, an even one with equal parities.
+        Ok(_) | Err(Error::ParityError) => {
+            if (cube.corner_parity() == cube.edge_parity()) == cube.center_parity() {
+                Ok(())
+            } else {
+                Err(stage_error(stage, &format!("invalid cube, {}", Error::ParityError)))
+            }
+        }
+        Err(e) => Err(stage_error(stage, &format!("invalid cube, {}", e))),
+    }
+}
+
+/// Check if the cube is valid and its centers are at the solved positions.
+pub(crate) fn check_cube_centers(stage: &'static str, cube: &CubieCube) -> Result<(), Error> {
+    check_cube(stage, cube)?;
+    if cube.center != SOLVED_CUBIE_CUBE.center {
+        return Err(stage_error(stage, "centers are not at the solved positions"));
+    }
+    Ok(())
+}
+
+/// Check a precondition of a stage, i.e, the previous steps are solved.
+pub(crate) fn check_solved(stage: &'static str, steps: &[(&str, bool)]) -> Result<(), Error> {
+    for (step, solved) in steps {
+        if !solved {
+            return Err(stage_error(stage, &format!("{} is not solved", step)));
+        }
+    }
+    Ok(())
+}
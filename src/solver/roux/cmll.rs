@@ -1,7 +1,10 @@
 use crate::cubie::{Corner, CubieCube};
+use crate::error::Error;
 use crate::facelet::Color;
-use crate::moves::Formula;
 use crate::moves::Move::{self, *};
+use crate::solver::{auf_moves, stage_error};
+
+use super::check_steps;
 use std::collections::HashMap;
 
 /// `CMLL` is the third step of the Roux method, after solving the first two blocks. The goal of this step is to solve the corners of the last layer without considering the M-slice.
@@ -20,201 +23,176 @@ use std::collections::HashMap;
 ///     let f = Formula::scramble();
 ///     let cc = cc.apply_formula(&f);
 ///     let mut fb = FBSolver::new(cc);
-///     let _fb = fb.solve();
+///     let _fb = fb.solve().unwrap();
 ///     assert!(fb.is_solved());
 ///     let mut sb = SBSolver::new(fb.cube);
-///     let _sb = sb.solve();
+///     let _sb = sb.solve().unwrap();
 ///     assert!(sb.is_solved());
 ///     let mut cmll = CMLLSolver::new(sb.cube);
-///     let _cmll = cmll.solve();
+///     let _cmll = cmll.solve().unwrap();
 ///     assert!(cmll.is_solved());
 ///     println!("
-///         Scramble: {:?}\nFirst Block: {:?}\nSecond Block: {:?}\nCMLL: {:?}",
-///         f.moves, _fb, _sb, _cmll
+///         Scramble: {:?}\nFirst Block: {:?}\nSecond Block: {:?}\nCMLL {:?}: {:?}",
+///         f.moves, _fb, _sb, cmll.case(), _cmll
 ///     );
 /// }
 /// ```
 
 pub struct CMLLSolver {
     pub cube: CubieCube,
-    algos: HashMap<String, Vec<Move>>,
+    algos: HashMap<String, (&'static str, Vec<Move>)>,
+    case: Option<&'static str>,
 }
 
 impl CMLLSolver {
     /// Construct the CMLLSolver.
     pub fn new(cube: CubieCube) -> Self {
         let mut algos = HashMap::new();
-        // solved
-        algos.insert("RFFLLBBR".to_string(), vec![]);
-        // o_adjacent_swap
+        algos.insert("RFFLLBBR".to_string(), ("skip", vec![]));
         algos.insert(
             "BRFLLBRF".to_string(),
-            vec![R, U, R3, F3, R, U, R3, U3, R3, F, R2, U3, R3],
+            ("o_adjacent_swap", vec![R, U, R3, F3, R, U, R3, U3, R3, F, R2, U3, R3]),
         );
-        // o_diagonal_swap
         algos.insert(
             "LBFLRFBR".to_string(),
-            vec![F, R, U3, R3, U3, R, U, R3, F3, R, U, R3, U3, R3, F, R, F3],
+            ("o_diagonal_swap", vec![F, R, U3, R3, U3, R, U, R3, F3, R, U, R3, U3, R3, F, R, F3]),
         );
-        // h_columns
         algos.insert(
             "RUURLUUL".to_string(),
-            vec![U3, R, U, R3, U, R, U3, R3, U, R, U2, R3],
+            ("h_columns", vec![U3, R, U, R3, U, R, U3, R3, U, R, U2, R3]),
         );
-        // h_rows
         algos.insert(
             "LUURRUUL".to_string(),
-            vec![F, R, U, R3, U3, R, U, R3, U3, R, U, R3, U3, F3],
+            ("h_rows", vec![F, R, U, R3, U3, R, U, R3, U3, R, U, R3, U3, F3]),
         );
-        // h_column
         algos.insert(
             "FUULLUUB".to_string(),
-            vec![U3, R, U2, R2, F, R, F3, U2, R3, F, R, F3],
+            ("h_column", vec![U3, R, U2, R2, F, R, F3, U2, R3, F, R, F3]),
         );
-        // h_row
         algos.insert(
             "LUURBUUB".to_string(),
-            vec![U2, Rw, U3, Rw2, D3, Rw, U3, Rw3, D, Rw2, U, Rw3],
+            ("h_row", vec![U2, Rw, U3, Rw2, D3, Rw, U3, Rw3, D, Rw2, U, Rw3]),
         );
-        // pi_right_bar
         algos.insert(
             "FULUULUB".to_string(),
-            vec![F, R, U, R3, U3, R, U, R3, U3, F3],
+            ("pi_right_bar", vec![F, R, U, R3, U3, R, U, R3, U3, F3]),
         );
-        // pi_back_slash
         algos.insert(
             "BURUUFUR".to_string(),
-            vec![U, F, R3, F3, R, U2, R, U3, R3, U, R, U2, R3],
+            ("pi_back_slash", vec![U, F, R3, F3, R, U2, R, U3, R3, U, R, U2, R3]),
         );
-        // pi_x_checkerboard
         algos.insert(
             "BUFUUFUB".to_string(),
-            vec![U3, R3, F, R, U, F, U3, R, U, R3, U3, F3],
+            ("pi_x_checkerboard", vec![U3, R3, F, R, U, F, U3, R, U, R3, U3, F3]),
         );
-        // pi_forward_slash
         algos.insert(
             "FURUUFUL".to_string(),
-            vec![R, U2, R3, U3, R, U, R3, U2, R3, F, R, F3],
+            ("pi_forward_slash", vec![R, U2, R3, U3, R, U, R3, U2, R3, F, R, F3]),
         );
-        // pi_columns
         algos.insert(
             "LURUULUR".to_string(),
-            vec![U3, Rw, U3, Rw2, D3, Rw, U, Rw3, D, Rw2, U, Rw3],
+            ("pi_columns", vec![U3, Rw, U3, Rw2, D3, Rw, U, Rw3, D, Rw2, U, Rw3]),
         );
-        // pi_left_bar
         algos.insert(
             "FURUULUF".to_string(),
-            vec![U3, R3, U3, R3, F, R, F3, R, U3, R3, U2, R],
+            ("pi_left_bar", vec![U3, R3, U3, R3, F, R, F3, R, U3, R3, U2, R]),
         );
-        // u_forward_slash
         algos.insert(
             "LBRFLUUB".to_string(),
-            vec![U2, R2, D, R3, U2, R, D3, R3, U2, R3],
+            ("u_forward_slash", vec![U2, R2, D, R3, U2, R, D3, R3, U2, R3]),
         );
-        // u_back_slash
-        algos.insert("BRFLFUUL".to_string(), vec![R2, D3, R, U2, R3, D, R, U2, R]);
-        // u_front_row
+        algos.insert("BRFLFUUL".to_string(), ("u_back_slash", vec![R2, D3, R, U2, R3, D, R, U2, R]));
         algos.insert(
             "RFFLBUUB".to_string(),
-            vec![R3, U3, R, U3, R3, U2, R2, U, R3, U, R, U2, R3],
+            ("u_front_row", vec![R3, U3, R, U3, R3, U2, R2, U, R3, U, R, U2, R3]),
         );
-        // u_rows
         algos.insert(
             "RFFLRUUL".to_string(),
-            vec![U3, F, R2, D, R3, U, R, D3, R2, U3, F3],
+            ("u_rows", vec![U3, F, R2, D, R3, U, R, D3, R2, U3, F3]),
         );
-        // u_x_checkerboard
         algos.insert(
             "FLRFBUUB".to_string(),
-            vec![U2, Rw, U3, Rw3, U, Rw3, D3, Rw, U3, Rw3, D, Rw],
+            ("u_x_checkerboard", vec![U2, Rw, U3, Rw3, U, Rw3, D3, Rw, U3, Rw3, D, Rw]),
         );
-        // u_back_row
-        algos.insert("LBFLFUUB".to_string(), vec![F, R, U, R3, U3, F3]);
-        // t_left_bar
-        algos.insert("LBRFUBLU".to_string(), vec![U3, R, U, R3, U3, R3, F, R, F3]);
-        // t_right_bar
-        algos.insert("FLBRURBU".to_string(), vec![U, L3, U3, L, U, L, F3, L3, F]);
-        // t_rows
+        algos.insert("LBFLFUUB".to_string(), ("u_back_row", vec![F, R, U, R3, U3, F3]));
+        algos.insert("LBRFUBLU".to_string(), ("t_left_bar", vec![U3, R, U, R3, U3, R3, F, R, F3]));
+        algos.insert("FLBRURBU".to_string(), ("t_right_bar", vec![U, L3, U3, L, U, L, F3, L3, F]));
         algos.insert(
             "BRRFUFBU".to_string(),
-            vec![R, U2, R3, U3, R, U3, R2, U2, R, U, R3, U, R],
+            ("t_rows", vec![R, U2, R3, U3, R, U3, R2, U2, R, U, R3, U, R]),
         );
-        // t_front_row
         algos.insert(
             "LBBRUFFU".to_string(),
-            vec![Rw3, U, Rw, U2, R2, F, R, F3, R],
+            ("t_front_row", vec![Rw3, U, Rw, U2, R2, F, R, F3, R]),
         );
-        // t_back_row
         algos.insert(
             "BRLBURLU".to_string(),
-            vec![Rw3, D3, Rw, U, Rw3, D, Rw, U3, Rw, U, Rw3],
+            ("t_back_row", vec![Rw3, D3, Rw, U, Rw3, D, Rw, U3, Rw, U, Rw3]),
         );
-        // t_columns
         algos.insert(
             "BRLBUFFU".to_string(),
-            vec![U2, Rw2, D3, Rw, U, Rw3, D, Rw2, U3, Rw3, U3, Rw],
+            ("t_columns", vec![U2, Rw2, D3, Rw, U, Rw3, D, Rw2, U3, Rw3, U3, Rw]),
         );
-        // s_left_bar
-        algos.insert("BRFULUBU".to_string(), vec![R, U, R3, U, R, U2, R3]);
-        // s_x_checkerboard
-        algos.insert("RFBULURU".to_string(), vec![L3, U2, L, U2, L, F3, L3, F]);
-        // s_forward_slash
-        algos.insert("RFLURUBU".to_string(), vec![F, R3, F3, R, U2, R, U2, R3]);
-        // s_columns
+        algos.insert("BRFULUBU".to_string(), ("s_left_bar", vec![R, U, R3, U, R, U2, R3]));
+        algos.insert("RFBULURU".to_string(), ("s_x_checkerboard", vec![L3, U2, L, U2, L, F3, L3, F]));
+        algos.insert("RFLURUBU".to_string(), ("s_forward_slash", vec![F, R3, F3, R, U2, R, U2, R3]));
         algos.insert(
             "RFBURULU".to_string(),
-            vec![R, U, R3, U3, R3, F, R, F3, R, U, R3, U, R, U2, R3],
+            ("s_columns", vec![R, U, R3, U3, R3, F, R, F3, R, U, R3, U, R, U2, R3]),
         );
-        // s_right_bar
         algos.insert(
             "LBLUFURU".to_string(),
-            vec![U2, R, U, R3, U, R3, F, R, F3, R, U2, R3],
+            ("s_right_bar", vec![U2, R, U, R3, U, R3, F, R, F3, R, U2, R3]),
         );
-        // s_back_slash
-        algos.insert("RFRULUBU".to_string(), vec![R, U3, L3, U, R3, U3, L]);
-        // as_right_bar
-        algos.insert("UBRFUFUL".to_string(), vec![U3, R, U2, R3, U3, R, U3, R3]);
-        // as_columns
+        algos.insert("RFRULUBU".to_string(), ("s_back_slash", vec![R, U3, L3, U, R3, U3, L]));
+        algos.insert("UBRFUFUL".to_string(), ("as_right_bar", vec![U3, R, U2, R3, U3, R, U3, R3]));
         algos.insert(
             "ULRFUBUF".to_string(),
-            vec![R2, D, R3, U, R, D3, R3, U, R3, U3, R, U3, R3],
+            ("as_columns", vec![R2, D, R3, U, R, D3, R3, U, R3, U3, R, U3, R3]),
         );
-        // as_back_slash
         algos.insert(
             "URFLUBUL".to_string(),
-            vec![U3, F3, Rw, U, Rw3, U2, Rw3, F2, Rw],
+            ("as_back_slash", vec![U3, F3, Rw, U, Rw3, U2, Rw3, F2, Rw]),
         );
-        // as_x_checkerboard
-        algos.insert("UFBRURUL".to_string(), vec![R, U2, R3, U2, R3, F, R, F3]);
-        // as_forward_slash
-        algos.insert("URBRUFUL".to_string(), vec![L3, U, R, U3, L, U, R3]);
-        // as_left_bar
+        algos.insert("UFBRURUL".to_string(), ("as_x_checkerboard", vec![R, U2, R3, U2, R3, F, R, F3]));
+        algos.insert("URBRUFUL".to_string(), ("as_forward_slash", vec![L3, U, R, U3, L, U, R3]));
         algos.insert(
             "ULFLURUB".to_string(),
-            vec![U2, R, U2, R3, F, R3, F3, R, U3, R, U3, R3],
+            ("as_left_bar", vec![U2, R, U2, R3, F, R3, F3, R, U3, R, U3, R3]),
         );
-        // l_mirror
-        algos.insert("RFLUBRUL".to_string(), vec![F, R, U3, R3, U3, R, U, R3, F3]);
-        // l_inverse
-        algos.insert("FLRULBUR".to_string(), vec![F, R3, F3, R, U, R, U3, R3]);
-        // l_pure
+        algos.insert("RFLUBRUL".to_string(), ("l_mirror", vec![F, R, U3, R3, U3, R, U, R3, F3]));
+        algos.insert("FLRULBUR".to_string(), ("l_inverse", vec![F, R3, F3, R, U, R, U3, R3]));
         algos.insert(
             "RFLULBUB".to_string(),
-            vec![U2, R, U, R3, U, R, U3, R3, U, R, U3, R3, U, R, U2, R3],
+            ("l_pure", vec![U2, R, U, R3, U, R, U3, R3, U, R, U3, R3, U, R, U2, R3]),
         );
-        // l_front_commutator
-        algos.insert("RFRUFLUL".to_string(), vec![R, U2, R, D, R3, U2, R, D3, R2]);
-        // l_diag
+        algos.insert("RFRUFLUL".to_string(), ("l_front_commutator", vec![R, U2, R, D, R3, U2, R, D3, R2]));
         algos.insert(
             "BRBUFLUR".to_string(),
-            vec![U2, R3, U3, R, U, R3, F3, R, U, R3, U3, R3, F, R2],
+            ("l_diag", vec![U2, R3, U3, R, U, R3, F3, R, U, R3, U3, R3, F, R2]),
         );
-        // l_back_commutator
         algos.insert(
             "BRLULBUR".to_string(),
-            vec![U3, R3, U2, R3, D3, R, U2, R3, D, R2],
+            ("l_back_commutator", vec![U3, R3, U2, R3, D3, R, U2, R3, D, R2]),
         );
-        Self { cube, algos }
+        Self {
+            cube,
+            algos,
+            case: None,
+        }
+    }
+
+    /// The name of the CMLL case applied by `solve`, eg. "pi_right_bar", or "skip" if only AUF was needed.
+    pub fn case(&self) -> Option<&str> {
+        self.case
+    }
+
+    /// The algorithm of the CMLL case `name`, eg. "pi_right_bar".
+    pub(crate) fn algorithm(&self, name: &str) -> Option<Vec<Move>> {
+        self.algos
+            .values()
+            .find(|(n, _)| *n == name)
+            .map(|(_, algo)| algo.clone())
     }
 
     /// Recognise which is Cube's CMLL case.
@@ -230,23 +208,12 @@ impl CMLLSolver {
         idx
     }
 
-    /// Solve the CMLL. Returns an `Vec<Move>`.
-    pub fn solve(&mut self) -> Vec<Move> {
-        let mut result = Vec::new();
+    /// Find the CMLL case, returns the pre-AUF (number of U turns), the case name, the algorithm and the post-AUF.
+    pub(crate) fn find_case(&mut self) -> Result<(usize, &'static str, Vec<Move>, usize), Error> {
+        check_steps("CMLL", &self.cube, 2)?;
+        let cube = self.cube;
         for i in 0..4 {
-            let mut i_put = match i {
-                1 => Formula {
-                    moves: vec![Move::U],
-                },
-                2 => Formula {
-                    moves: vec![Move::U2],
-                },
-                3 => Formula {
-                    moves: vec![Move::U3],
-                },
-                _ => Formula { moves: vec![] },
-            };
-            self.cube = self.cube.apply_formula(&i_put);
+            self.cube = cube.apply_moves(&auf_moves(i));
             let case = self.recognise();
             for r in 0..4 {
                 let case: String = case
@@ -254,57 +221,33 @@ impl CMLLSolver {
                     .map(|c| rotate_color(*c, r))
                     .map(|c| format!("{:?}", c))
                     .collect();
-                let algo = self.algos.get(&case);
-                if algo.is_some() {
-                    let algo = Formula {
-                        moves: algo.unwrap().clone(),
-                    };
+                if let Some((name, algo)) = self.algos.get(&case) {
                     for j in 0..4 {
-                        let mut j_put = match j {
-                            1 => Formula {
-                                moves: vec![Move::U],
-                            },
-                            2 => Formula {
-                                moves: vec![Move::U2],
-                            },
-                            3 => Formula {
-                                moves: vec![Move::U3],
-                            },
-                            _ => Formula { moves: vec![] },
-                        };
-                        self.cube = self.cube.apply_formula(&j_put);
-                        self.cube = self.cube.apply_formula(&algo);
+                        let put_cube = cube.apply_moves(&auf_moves(i + j)).apply_moves(algo);
                         for k in 0..4 {
-                            let mut k_put = match k {
-                                1 => Formula {
-                                    moves: vec![Move::U],
-                                },
-                                2 => Formula {
-                                    moves: vec![Move::U2],
-                                },
-                                3 => Formula {
-                                    moves: vec![Move::U3],
-                                },
-                                _ => Formula { moves: vec![] },
-                            };
-                            self.cube = self.cube.apply_formula(&k_put);
+                            self.cube = put_cube.apply_moves(&auf_moves(k));
                             if self.is_solved() {
-                                result.append(&mut i_put.moves);
-                                result.append(&mut j_put.moves);
-                                result.append(&mut self.algos[&case].clone());
-                                result.append(&mut k_put.moves);
-                                return result;
+                                self.cube = cube;
+                                return Ok(((i + j) % 4, *name, algo.clone(), k));
                             }
-                            self.cube = self.cube.apply_formula(&k_put.inverse());
                         }
-                        self.cube = self.cube.apply_formula(&algo.inverse());
-                        self.cube = self.cube.apply_formula(&j_put.inverse());
                     }
                 }
             }
-            self.cube = self.cube.apply_formula(&i_put.inverse());
         }
-        result
+        self.cube = cube;
+        Err(stage_error("CMLL", "unrecognised CMLL case"))
+    }
+
+    /// Solve the CMLL. Returns an `Vec<Move>`.
+    pub fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        let (i, name, mut algo, k) = self.find_case()?;
+        let mut result = auf_moves(i);
+        result.append(&mut algo);
+        result.append(&mut auf_moves(k));
+        self.cube = self.cube.apply_moves(&result);
+        self.case = Some(name);
+        Ok(result)
     }
 
     /// Check if Cube is solved.
@@ -388,13 +331,13 @@ mod tests {
         let f = Formula::scramble();
         let cc = cc.apply_formula(&f);
         let mut fb = FBSolver::new(cc);
-        let _fb = fb.solve();
+        let _fb = fb.solve().unwrap();
         assert!(fb.is_solved());
         let mut sb = SBSolver::new(fb.cube);
-        let _sb = sb.solve();
+        let _sb = sb.solve().unwrap();
         assert!(sb.is_solved());
         let mut cmll = CMLLSolver::new(sb.cube);
-        let _cmll = cmll.solve();
+        let _cmll = cmll.solve().unwrap();
         assert!(cmll.is_solved());
         println!(
             "Scramble: {:?}\nFirst Block: {:?}\nSecond Block: {:?}\nCMLL: {:?}",
@@ -2,10 +2,12 @@ use std::collections::HashMap;
 
 use crate::{
     cubie::{Corner, CubieCube, Edge, SOLVED_CUBIE_CUBE},
+    error::Error,
     moves::Move::{self, *},
+    solver::stage_error,
 };
 
-use super::{get_available_move, Pruner, SolverBase, SolverConfig};
+use super::{check_steps, get_available_move, Pruner, SolverBase, SolverConfig};
 
 /// FBSolver for solve Roux's First Block(a 1x2x3 block at left bottom).
 /// # Example
@@ -21,7 +23,7 @@ use super::{get_available_move, Pruner, SolverBase, SolverConfig};
 ///     println!("Scramble: {:?}", f);
 ///     let cc = cc.apply_formula(&f);
 ///     let mut fb = FBSolver::new(cc);
-///     let solution = fb.solve();
+///     let solution = fb.solve().unwrap();
 ///     assert!(fb.is_solved());
 ///     println!("First Block Solution: {:?}", solution);
 /// }
@@ -59,30 +61,11 @@ impl SolverBase for FBSolver {
 
     /// Check if First Block is solved.
     fn is_solved(&self) -> bool {
-        if self.cube.center[4] != SOLVED_CUBIE_CUBE.center[4] {
-            return false;
-        }
-        let (corners, edges) = FBSolver::get_state(&self.cube);
-        let mut solved = 0;
-        for c in corners {
-            match c {
-                (Corner::DLF, 5, 0) | (Corner::DBL, 6, 0) => solved += 1,
-                _ => {}
-            };
-        }
-        for e in edges {
-            match e {
-                (Edge::FL, 9, 0) | (Edge::BL, 10, 0) | (Edge::DL, 6, 0) => solved += 1,
-                _ => {}
-            };
-        }
-        if solved == 5 {
-            return true;
-        }
-        false
+        FBSolver::is_block_solved(&self.cube)
     }
 
-    fn solve(&mut self) -> Vec<Move> {
+    fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("First block", &self.cube, 0)?;
         let mut solution = Vec::new();
         for i in self.config.min_depth..=self.config.max_depth {
             let min_depth = self.config.min_depth;
@@ -99,11 +82,39 @@ impl SolverBase for FBSolver {
             }
         }
         self.cube = self.cube.apply_moves(&solution);
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("First block", "no solution found"));
+        }
+        Ok(solution)
     }
 }
 
 impl FBSolver {
+    /// Check if the First Block of a cube is solved.
+    pub(crate) fn is_block_solved(cube: &CubieCube) -> bool {
+        if cube.center[4] != SOLVED_CUBIE_CUBE.center[4] {
+            return false;
+        }
+        let (corners, edges) = FBSolver::get_state(cube);
+        let mut solved = 0;
+        for c in corners {
+            match c {
+                (Corner::DLF, 5, 0) | (Corner::DBL, 6, 0) => solved += 1,
+                _ => {}
+            };
+        }
+        for e in edges {
+            match e {
+                (Edge::FL, 9, 0) | (Edge::BL, 10, 0) | (Edge::DL, 6, 0) => solved += 1,
+                _ => {}
+            };
+        }
+        if solved == 5 {
+            return true;
+        }
+        false
+    }
+
     fn get_state(state: &CubieCube) -> (Vec<(Corner, u8, u8)>, Vec<(Edge, u8, u8)>) {
         let mut corners = Vec::new();
         for i in 0..8 {
@@ -198,7 +209,7 @@ mod tests {
         println!("Scramble: {:?}", _f);
         let cc = cc.apply_formula(&_f);
         let mut solver = FBSolver::new(cc);
-        let _s = solver.solve();
+        let _s = solver.solve().unwrap();
         assert!(solver.is_solved());
         println!("First Block Solution: {:?}", _s);
     }
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 
 use crate::cubie::{CubieCube, SOLVED_CUBIE_CUBE};
+use crate::error::Error;
 use crate::facelet::Color;
 use crate::moves::Move::{self, *};
+use crate::solver::stage_error;
 
-use super::{get_available_move, Pruner, SolverBase, SolverConfig};
+use super::{check_steps, get_available_move, Pruner, SolverBase, SolverConfig};
 
 /// LSE(Last Six Edges) is the fourth step of the Roux method.
 /// LSE typically split to 3 substeps (called 4a, 4b, and 4c).
@@ -26,16 +28,16 @@ use super::{get_available_move, Pruner, SolverBase, SolverConfig};
 ///     let f = Formula::scramble();
 ///     let cc = cc.apply_formula(&f);
 ///     let mut fb = FBSolver::new(cc);
-///     let _fb = fb.solve();
+///     let _fb = fb.solve().unwrap();
 ///     assert!(fb.is_solved());
 ///     let mut sb = SBSolver::new(fb.cube);
-///     let _sb = sb.solve();
+///     let _sb = sb.solve().unwrap();
 ///     assert!(sb.is_solved());
 ///     let mut cmll = CMLLSolver::new(sb.cube);
-///     let _cmll = cmll.solve();
+///     let _cmll = cmll.solve().unwrap();
 ///     assert!(cmll.is_solved());
 ///     let mut lse = LSESolver::new(cmll.cube);
-///     let _lse = lse.solve();
+///     let _lse = lse.solve().unwrap();
 ///     assert!(lse.is_solved());
 ///     println!("
 ///         Scramble: {:?}\nFirst Block: {:?}\nSecond Block: {:?}\nCMLL: {:?}\nLSE: {:?}",
@@ -79,7 +81,8 @@ impl SolverBase for LSESolver {
         self.cube == SOLVED_CUBIE_CUBE
     }
 
-    fn solve(&mut self) -> Vec<Move> {
+    fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("LSE", &self.cube, 3)?;
         let mut solution = Vec::new();
         for i in self.config.min_depth..=self.config.max_depth {
             let min_depth = self.config.min_depth;
@@ -89,7 +92,10 @@ impl SolverBase for LSESolver {
             }
         }
         self.cube = self.cube.apply_moves(&solution);
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("LSE", "no solution found"));
+        }
+        Ok(solution)
     }
 }
 
@@ -156,16 +162,16 @@ mod tests {
         let f = Formula::scramble();
         let cc = cc.apply_formula(&f);
         let mut fb = FBSolver::new(cc);
-        let _fb = fb.solve();
+        let _fb = fb.solve().unwrap();
         assert!(fb.is_solved());
         let mut sb = SBSolver::new(fb.cube);
-        let _sb = sb.solve();
+        let _sb = sb.solve().unwrap();
         assert!(sb.is_solved());
         let mut cmll = CMLLSolver::new(sb.cube);
-        let _cmll = cmll.solve();
+        let _cmll = cmll.solve().unwrap();
         assert!(cmll.is_solved());
         let mut lse = LSESolver::new(cmll.cube);
-        let _lse = lse.solve();
+        let _lse = lse.solve().unwrap();
         assert!(lse.is_solved());
         println!(
             "Scramble: {:?}\nFirst Block: {:?}\nSecond Block: {:?}\nCMLL: {:?}\nLSE: {:?}",
@@ -30,7 +30,9 @@ pub use sb::SBSolver;
 
 use crate::{
     cubie::{CubieCube, SOLVED_CUBIE_CUBE},
-    moves::Move::{self, *},
+    error::Error,
+    moves::{Formula, Move::{self, *}},
+    solver::{check_cube, check_solved, stage_error, Solution, SolveReport, Solver},
 };
 
 /// RouxSolver for solve a cube use Roux method.
@@ -38,6 +40,7 @@ use crate::{
 /// ```rust
 /// use rcuber::cubie::CubieCube;
 /// use rcuber::moves::Formula;
+/// use rcuber::solver::Solver;
 /// use rcuber::solver::roux::RouxSolver;
 ///
 /// fn main() {
@@ -45,7 +48,7 @@ use crate::{
 ///     let f = Formula::scramble();
 ///     let cc = cc.apply_formula(&f);
 ///     let mut roux = RouxSolver::new(cc);
-///     let _roux = roux.solve();
+///     let _roux = roux.solve().unwrap();
 ///     assert!(roux.is_solved());
 ///     println!("Scramble: {:?}\nRoux Solution: {:?}", f.moves, _roux);
 /// }
@@ -56,41 +59,49 @@ pub struct RouxSolver {
     pub cube: CubieCube,
 }
 
-impl RouxSolver {
-    pub fn new(cube: CubieCube) -> Self {
+impl Solver for RouxSolver {
+    fn new(cube: CubieCube) -> Self {
         Self { cube }
     }
 
     /// Check if cube is solved.
-    pub fn is_solved(&self) -> bool {
+    fn is_solved(&self) -> bool {
         self.cube == SOLVED_CUBIE_CUBE
     }
 
-    /// Solve the cube.
-    pub fn solve(&mut self) -> Vec<Move> {
-        let mut result = Vec::new();
+    /// Solve the cube, step by step: First Block, Second Block, CMLL, LSE. The moves are cancelled across the stages.
+    fn solve(&mut self) -> Result<Solution, Error> {
+        let solution = Formula::from(self.solve_report()?.solution());
+        Ok(Solution::from(solution.optimise()))
+    }
+
+    fn name(&self) -> &'static str {
+        "Roux"
+    }
+}
+
+impl RouxSolver {
+    /// Solve the cube and report each stage: First Block, Second Block, CMLL with its case and LSE.
+    pub fn solve_report(&mut self) -> Result<SolveReport, Error> {
+        let mut report = SolveReport::default();
         let mut fb = FBSolver::new(self.cube);
-        let mut _fb = fb.solve();
-        assert!(fb.is_solved());
+        let _fb = fb.solve()?;
         self.cube = fb.cube;
-        result.append(&mut _fb);
+        report.push("First block".to_string(), _fb, None, self.cube);
         let mut sb = SBSolver::new(self.cube);
-        let mut _sb = sb.solve();
-        assert!(sb.is_solved());
+        let _sb = sb.solve()?;
         self.cube = sb.cube;
-        result.append(&mut _sb);
+        report.push("Second block".to_string(), _sb, None, self.cube);
         let mut cmll = CMLLSolver::new(self.cube);
-        let mut _cmll = cmll.solve();
-        assert!(cmll.is_solved());
+        let _cmll = cmll.solve()?;
         self.cube = cmll.cube;
-        result.append(&mut _cmll);
+        let case = cmll.case().unwrap_or("skip").to_string();
+        report.push(format!("CMLL {}", case), _cmll, Some(case), self.cube);
         let mut lse = LSESolver::new(self.cube);
-        let mut _lse = lse.solve();
-        assert!(lse.is_solved());
+        let _lse = lse.solve()?;
         self.cube = lse.cube;
-        result.append(&mut _lse);
-        assert!(self.is_solved());
-        result
+        report.push("LSE".to_string(), _lse, None, self.cube);
+        Ok(report)
     }
 }
 
@@ -107,7 +118,7 @@ pub struct SolverConfig {
 pub trait SolverBase {
     fn new(cube: CubieCube) -> Self;
     fn is_solved(&self) -> bool;
-    fn solve(&mut self) -> Vec<Move>;
+    fn solve(&mut self) -> Result<Vec<Move>, Error>;
     fn solve_depth(
         cube: &CubieCube,
         min_depth: i32,
@@ -234,6 +245,20 @@ pub trait Pruner {
     }
 }
 
+/// Check if the cube is valid and the Roux steps before the `step`th one are solved.
+fn check_steps(stage: &'static str, cube: &CubieCube, step: usize) -> Result<(), Error> {
+    check_cube(stage, cube)?;
+    if cube.center[1] != SOLVED_CUBIE_CUBE.center[1] || cube.center[4] != SOLVED_CUBIE_CUBE.center[4] {
+        return Err(stage_error(stage, "L/R centers are not at the solved positions"));
+    }
+    let steps = [
+        ("First block", FBSolver::is_block_solved(cube)),
+        ("Second block", SBSolver::is_block_solved(cube)),
+        ("CMLL", CMLLSolver::new(*cube).is_solved()),
+    ];
+    check_solved(stage, &steps[..step])
+}
+
 fn get_available_move(m: Move, moveset: &Vec<Move>) -> Vec<Move> {
     match m {
         U | U2 | U3 => moveset
@@ -282,8 +307,13 @@ fn get_available_move(m: Move, moveset: &Vec<Move>) -> Vec<Move> {
 
 #[cfg(test)]
 mod tests {
-    use super::RouxSolver;
-    use crate::{cubie::CubieCube, moves::Formula};
+    use super::{CMLLSolver, RouxSolver};
+    use crate::{
+        cubie::CubieCube,
+        error::Error,
+        moves::{Formula, Move::*},
+        solver::Solver,
+    };
 
     #[test]
     fn test_roux() {
@@ -291,8 +321,69 @@ mod tests {
         let f = Formula::scramble();
         let cc = cc.apply_formula(&f);
         let mut roux = RouxSolver::new(cc);
-        let _roux = roux.solve();
+        let _roux = roux.solve().unwrap();
         assert!(roux.is_solved());
         println!("Scramble: {:?}\nRoux Solution: {:?}", f.moves, _roux);
     }
+
+    #[test]
+    fn test_roux_report() {
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        let mut roux = RouxSolver::new(cc);
+        let report = roux.solve_report().unwrap();
+        let names: Vec<&str> = report.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names[..2], ["First block", "Second block"]);
+        assert!(names[2].starts_with("CMLL "));
+        assert_eq!(names[3], "LSE");
+        assert_eq!(report.stages[3].cube, CubieCube::default());
+        assert_eq!(cc.apply_moves(&report.solution().moves), CubieCube::default());
+
+        let pi = vec![F, R, U, R3, U3, R, U, R3, U3, F3];
+        let cc = CubieCube::default().apply_formula(&Formula { moves: pi }.inverse());
+        let report = RouxSolver::new(cc).solve_report().unwrap();
+        assert_eq!(report.stages[2].name, "CMLL pi_right_bar");
+    }
+
+    #[test]
+    fn test_roux_stage_error() {
+        let cc = CubieCube::default().apply_move(z);
+        let mut roux = RouxSolver::new(cc);
+        match roux.solve() {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "First block failed: L/R centers are not at the solved positions"
+            ),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+
+        // An odd center permutation with equal corner and edge parities.
+        let mut cc = CubieCube::default().apply_move(M);
+        cc.ep.swap(0, 2);
+        let mut roux = RouxSolver::new(cc);
+        match roux.solve() {
+            Err(e) => assert!(e.to_string().starts_with("First block failed: invalid cube"), "{}", e),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+
+        // The centers mirrored across the plane through the U, D centers and the UFR, UBL corners,
+        // an even center permutation.
+        let mut cc = CubieCube::default();
+        cc.center.swap(1, 2);
+        cc.center.swap(4, 5);
+        let mut roux = RouxSolver::new(cc);
+        match roux.solve() {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "First block failed: invalid cube, the centers are not in any orientation"
+            ),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+
+        let cc = CubieCube::default().apply_moves(&[R, U, R3]);
+        let mut cmll = CMLLSolver::new(cc);
+        assert!(matches!(
+            cmll.solve(),
+            Err(Error::StageFailed { stage: "CMLL", .. })
+        ));
+    }
 }
@@ -2,10 +2,12 @@ use std::collections::HashMap;
 
 use crate::{
     cubie::{Corner, CubieCube, Edge, SOLVED_CUBIE_CUBE},
+    error::Error,
     moves::Move::{self, *},
+    solver::stage_error,
 };
 
-use super::{get_available_move, Pruner, SolverBase, SolverConfig};
+use super::{check_steps, get_available_move, Pruner, SolverBase, SolverConfig};
 
 /// SBSolver for solve Roux's Second Block(a 1x2x3 block at right bottom).
 /// # Example
@@ -22,11 +24,11 @@ use super::{get_available_move, Pruner, SolverBase, SolverConfig};
 ///     println!("Scramble: {:?}", f);
 ///     let cc = cc.apply_formula(&f);
 ///     let mut fb = FBSolver::new(cc);
-///     let solution = fb.solve();
+///     let solution = fb.solve().unwrap();
 ///     assert!(fb.is_solved());
 ///     println!("First Block Solution: {:?}", solution);
 ///     let mut sb = SBSolver::new(fb.cube);
-///     let solution = sb.solve();
+///     let solution = sb.solve().unwrap();
 ///     assert!(sb.is_solved());
 ///     println!("Second Block Solution: {:?}", solution);
 /// }
@@ -64,30 +66,11 @@ impl SolverBase for SBSolver {
 
     /// Check if Second Block is solved.
     fn is_solved(&self) -> bool {
-        if self.cube.center[1] != SOLVED_CUBIE_CUBE.center[1] {
-            return false;
-        }
-        let (corners, edges) = SBSolver::get_state(&self.cube);
-        let mut solved = 0;
-        for c in corners {
-            match c {
-                (Corner::DFR, 4, 0) | (Corner::DRB, 7, 0) => solved += 1,
-                _ => {}
-            };
-        }
-        for e in edges {
-            match e {
-                (Edge::DR, 4, 0) | (Edge::FR, 8, 0) | (Edge::BR, 11, 0) => solved += 1,
-                _ => {}
-            };
-        }
-        if solved == 5 {
-            return true;
-        }
-        false
+        SBSolver::is_block_solved(&self.cube)
     }
 
-    fn solve(&mut self) -> Vec<Move> {
+    fn solve(&mut self) -> Result<Vec<Move>, Error> {
+        check_steps("Second block", &self.cube, 1)?;
         let mut solution = Vec::new();
         for i in self.config.min_depth..=self.config.max_depth {
             let min_depth = self.config.min_depth;
@@ -104,11 +87,39 @@ impl SolverBase for SBSolver {
             }
         }
         self.cube = self.cube.apply_moves(&solution);
-        solution
+        if !self.is_solved() {
+            return Err(stage_error("Second block", "no solution found"));
+        }
+        Ok(solution)
     }
 }
 
 impl SBSolver {
+    /// Check if the Second Block of a cube is solved.
+    pub(crate) fn is_block_solved(cube: &CubieCube) -> bool {
+        if cube.center[1] != SOLVED_CUBIE_CUBE.center[1] {
+            return false;
+        }
+        let (corners, edges) = SBSolver::get_state(cube);
+        let mut solved = 0;
+        for c in corners {
+            match c {
+                (Corner::DFR, 4, 0) | (Corner::DRB, 7, 0) => solved += 1,
+                _ => {}
+            };
+        }
+        for e in edges {
+            match e {
+                (Edge::DR, 4, 0) | (Edge::FR, 8, 0) | (Edge::BR, 11, 0) => solved += 1,
+                _ => {}
+            };
+        }
+        if solved == 5 {
+            return true;
+        }
+        false
+    }
+
     fn get_state(state: &CubieCube) -> (Vec<(Corner, u8, u8)>, Vec<(Edge, u8, u8)>) {
         let mut corners = Vec::new();
         for i in 0..8 {
@@ -202,11 +213,11 @@ mod tests {
         println!("Scramble: {:?}", _f);
         let cc = cc.apply_formula(&_f);
         let mut fb = FBSolver::new(cc);
-        let _f = fb.solve();
+        let _f = fb.solve().unwrap();
         assert!(fb.is_solved());
         println!("First Block Solution: {:?}", _f);
         let mut sb = SBSolver::new(fb.cube);
-        let _s = sb.solve();
+        let _s = sb.solve().unwrap();
         println!("Second Block Solution: {:?}", _s);
         assert!(sb.is_solved());
     }
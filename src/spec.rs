@@ -0,0 +1,353 @@
+//! # State spec
+//! A textual constraint spec of the cube states, clauses `kind: pieces` separated by ';':
+//! * `solved`: the pieces are at their positions and oriented.
+//! * `placed`: the pieces are at their positions, in any orientation.
+//! * `oriented`: the pieces are oriented, at any free position.
+//! * `random`: the pieces are at any free position, in any orientation.
+//!
+//! The pieces are named by their faces in any order, eg. "DFR" or "RDF", or by groups:
+//! "U-layer edges", "D-layer corners", "F-layer"(its corners and edges), "E-slice edges"(M, E or S),
+//! "edges", "corners" and "rest"(the pieces of no other clause). The pieces of no clause are random.
+//!
+//! The states are sampled exactly uniformly, `count` gives the number of the matching states.
+//! # Example
+//! ```rust
+//! use rand::SeedableRng;
+//! use rand_chacha::ChaCha8Rng;
+//! use rcuber::spec::StateSpec;
+//!
+//! fn main() {
+//!     let spec: StateSpec = "solved: D-layer, E-slice edges; random: rest".parse().unwrap();
+//!     assert_eq!(spec.count(), 62208);
+//!     let cc = spec.sample(&mut ChaCha8Rng::seed_from_u64(1));
+//!     assert!(spec.matches(&cc));
+//!     let spec: StateSpec = "solved: DF DR DB DL DFR; oriented: U-layer edges".parse().unwrap();
+//!     assert!(spec.matches(&spec.sample(&mut rand::thread_rng())));
+//! }
+//! ```
+
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::constants::{ALL_CORNERS, ALL_EDGES};
+use crate::cubie::{CubieCube, SOLVED_CUBIE_CUBE};
+use crate::error::Error;
+use crate::generator::Generator;
+
+/// The constraint of a piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    Solved,
+    Placed,
+    Oriented,
+    Random,
+}
+
+impl Constraint {
+    /// The piece may leave its position.
+    fn is_free(self) -> bool {
+        matches!(self, Constraint::Oriented | Constraint::Random)
+    }
+
+    /// The piece may be in any orientation.
+    fn is_unoriented(self) -> bool {
+        matches!(self, Constraint::Placed | Constraint::Random)
+    }
+}
+
+/// The constraints of the 8 corners and 12 edges, by the pieces.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StateSpec {
+    pub corners: [Constraint; 8],
+    pub edges: [Constraint; 12],
+}
+
+impl Default for StateSpec {
+    fn default() -> Self {
+        Self {
+            corners: [Constraint::Random; 8],
+            edges: [Constraint::Random; 12],
+        }
+    }
+}
+
+/// The corners and edges of a group of pieces, `None` for the rest.
+type Pieces = Option<(Vec<usize>, Vec<usize>)>;
+
+/// Parse the group of pieces at the start of the `tokens`, returns the pieces and the number of tokens used.
+fn pieces(tokens: &[&str]) -> Result<(Pieces, usize), Error> {
+    let invalid = || Error::InvalidStateSpec {
+        token: tokens[0].to_string(),
+    };
+    let letters = |name: String| {
+        let mut chars: Vec<char> = name.chars().collect();
+        chars.sort();
+        chars
+    };
+    let corners = |f: &dyn Fn(&str) -> bool| -> Vec<usize> {
+        (0..8).filter(|c| f(&ALL_CORNERS[*c].to_string())).collect()
+    };
+    let edges = |f: &dyn Fn(&str) -> bool| -> Vec<usize> {
+        (0..12).filter(|e| f(&ALL_EDGES[*e].to_string())).collect()
+    };
+    let kind = tokens.get(1).copied();
+    let (selected, used) = match tokens[0] {
+        "rest" => return Ok((None, 1)),
+        "corners" => ((corners(&|_| true), Vec::new()), 1),
+        "edges" => ((Vec::new(), edges(&|_| true)), 1),
+        token => match token.split_once('-') {
+            Some((face, "layer")) if face.len() == 1 && "URFDLB".contains(face) => {
+                let c = corners(&|name| name.contains(face));
+                let e = edges(&|name| name.contains(face));
+                match kind {
+                    Some("corners") => ((c, Vec::new()), 2),
+                    Some("edges") => ((Vec::new(), e), 2),
+                    _ => ((c, e), 1),
+                }
+            }
+            Some((slice, "slice")) => {
+                let faces = match slice {
+                    "M" => "LR",
+                    "E" => "UD",
+                    "S" => "FB",
+                    _ => return Err(invalid()),
+                };
+                let e = edges(&|name| !name.chars().any(|c| faces.contains(c)));
+                match kind {
+                    Some("edges") => ((Vec::new(), e), 2),
+                    _ => ((Vec::new(), e), 1),
+                }
+            }
+            Some(_) => return Err(invalid()),
+            None => {
+                let piece = letters(token.to_string());
+                let c = corners(&|name| letters(name.to_string()) == piece);
+                let e = edges(&|name| letters(name.to_string()) == piece);
+                if c.is_empty() && e.is_empty() {
+                    return Err(invalid());
+                }
+                ((c, e), 1)
+            }
+        },
+    };
+    Ok((Some(selected), used))
+}
+
+impl FromStr for StateSpec {
+    type Err = Error;
+
+    /// Parse a spec, eg. "solved: DF DR DB DL DFR; oriented: U-layer edges; random: rest".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut corners = [None; 8];
+        let mut edges = [None; 12];
+        let mut rest = None;
+        for clause in s.split(';').map(str::trim).filter(|c| !c.is_empty()) {
+            let invalid = |token: &str| Error::InvalidStateSpec {
+                token: token.to_string(),
+            };
+            let (kind, list) = clause.split_once(':').ok_or_else(|| invalid(clause))?;
+            let constraint = match kind.trim() {
+                "solved" => Constraint::Solved,
+                "placed" => Constraint::Placed,
+                "oriented" => Constraint::Oriented,
+                "random" => Constraint::Random,
+                kind => return Err(invalid(kind)),
+            };
+            let tokens: Vec<&str> = list
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|t| !t.is_empty())
+                .collect();
+            let mut i = 0;
+            while i < tokens.len() {
+                let (selected, used) = pieces(&tokens[i..])?;
+                match selected {
+                    None if rest.is_none() => rest = Some(constraint),
+                    None => return Err(invalid(tokens[i])),
+                    Some((cs, es)) => {
+                        for c in cs {
+                            if corners[c].replace(constraint).is_some() {
+                                return Err(invalid(tokens[i]));
+                            }
+                        }
+                        for e in es {
+                            if edges[e].replace(constraint).is_some() {
+                                return Err(invalid(tokens[i]));
+                            }
+                        }
+                    }
+                }
+                i += used;
+            }
+        }
+        let rest = rest.unwrap_or(Constraint::Random);
+        Ok(Self {
+            corners: corners.map(|c| c.unwrap_or(rest)),
+            edges: edges.map(|e| e.unwrap_or(rest)),
+        })
+    }
+}
+
+impl StateSpec {
+    /// The number of the states matching the spec.
+    pub fn count(&self) -> u128 {
+        let factorial = |n: usize| (1..=n as u128).product::<u128>();
+        // The even and odd permutations of the free pieces.
+        let parities = |n: usize| match n {
+            0 | 1 => (1, 0),
+            _ => (factorial(n) / 2, factorial(n) / 2),
+        };
+        let (even_c, odd_c) = parities(self.corners.iter().filter(|c| c.is_free()).count());
+        let (even_e, odd_e) = parities(self.edges.iter().filter(|e| e.is_free()).count());
+        let orientations = |n: usize, ori: u128| ori.pow(n.saturating_sub(1) as u32);
+        let twists = self.corners.iter().filter(|c| c.is_unoriented()).count();
+        let flips = self.edges.iter().filter(|e| e.is_unoriented()).count();
+        (even_c * even_e + odd_c * odd_e) * orientations(twists, 3) * orientations(flips, 2)
+    }
+
+    /// Sample a state matching the spec, uniformly with the `rng`.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> CubieCube {
+        let mut cc = CubieCube::default();
+        let free_c: Vec<usize> = (0..8).filter(|c| self.corners[*c].is_free()).collect();
+        let free_e: Vec<usize> = (0..12).filter(|e| self.edges[*e].is_free()).collect();
+        Generator::shuffle(&mut cc.cp, &free_c, rng);
+        Generator::shuffle(&mut cc.ep, &free_e, rng);
+        if cc.corner_parity() != cc.edge_parity() {
+            // A fixed transposition maps the permutations of one parity onto the other one.
+            match free_e.len() >= 2 {
+                true => cc.ep.swap(free_e[0], free_e[1]),
+                false => cc.cp.swap(free_c[0], free_c[1]),
+            }
+        }
+        let twists: Vec<usize> = (0..8)
+            .filter(|i| self.corners[cc.cp[*i] as usize].is_unoriented())
+            .collect();
+        let flips: Vec<usize> = (0..12)
+            .filter(|i| self.edges[cc.ep[*i] as usize].is_unoriented())
+            .collect();
+        Generator::twist(&mut cc.co, &twists, 3, rng);
+        Generator::twist(&mut cc.eo, &flips, 2, rng);
+        cc
+    }
+
+    /// Check if the `cube` matches the spec.
+    pub fn matches(&self, cube: &CubieCube) -> bool {
+        let corners = (0..8).all(|i| {
+            let c = cube.cp[i] as usize;
+            match self.corners[c] {
+                Constraint::Solved => c == i && cube.co[i] == 0,
+                Constraint::Placed => c == i,
+                Constraint::Oriented => self.corners[i].is_free() && cube.co[i] == 0,
+                Constraint::Random => self.corners[i].is_free(),
+            }
+        });
+        let edges = (0..12).all(|i| {
+            let e = cube.ep[i] as usize;
+            match self.edges[e] {
+                Constraint::Solved => e == i && cube.eo[i] == 0,
+                Constraint::Placed => e == i,
+                Constraint::Oriented => self.edges[i].is_free() && cube.eo[i] == 0,
+                Constraint::Random => self.edges[i].is_free(),
+            }
+        });
+        corners && edges && cube.center == SOLVED_CUBIE_CUBE.center && cube.verify().is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::constants::N_STATES;
+    use crate::recognition;
+
+    fn spec(s: &str) -> StateSpec {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(spec(""), StateSpec::default());
+        assert_eq!(spec("random: rest"), StateSpec::default());
+        let s = spec("solved: DF DR DB DL RDF; oriented: U-layer edges");
+        assert_eq!(s.corners[4], Constraint::Solved);
+        assert_eq!(
+            s.edges[..6],
+            [
+                Constraint::Oriented,
+                Constraint::Oriented,
+                Constraint::Oriented,
+                Constraint::Oriented,
+                Constraint::Solved,
+                Constraint::Solved
+            ]
+        );
+        assert_eq!(s.edges[8], Constraint::Random);
+        let s = spec("placed: M-slice edges; solved: rest");
+        assert_eq!(
+            s.edges.iter().filter(|e| **e == Constraint::Placed).count(),
+            4
+        );
+        assert_eq!(s.corners, [Constraint::Solved; 8]);
+        for bad in [
+            "solved DF",
+            "fixed: DF",
+            "solved: DX",
+            "solved: DF; random: FD",
+            "solved: Q-layer",
+            "solved: rest; random: rest",
+            "solved: corners, D-layer",
+        ] {
+            assert!(bad.parse::<StateSpec>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_count() {
+        assert_eq!(StateSpec::default().count(), N_STATES);
+        assert_eq!(spec("oriented: edges").count(), N_STATES / 2048);
+        assert_eq!(spec("solved: D-layer, E-slice edges").count(), 62208);
+        assert_eq!(spec("solved: rest").count(), 1);
+        assert_eq!(spec("placed: corners; solved: rest").count(), 2187);
+        assert_eq!(
+            spec("solved: corners, D-layer edges, E-slice edges").count(),
+            12 * 8
+        );
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = Generator::seeded_rng(3);
+        for s in [
+            "",
+            "oriented: edges",
+            "solved: DF DR DB DL DFR; oriented: U-layer edges",
+            "solved: rest; random: UR UF",
+            "placed: corners",
+        ] {
+            let s = spec(s);
+            for _ in 0..50 {
+                assert!(s.matches(&s.sample(&mut rng)));
+            }
+        }
+        // The 12 states of 3 free edges are equally likely.
+        let s = spec("solved: rest; random: UR UF UL");
+        let mut counts = HashMap::new();
+        for _ in 0..1200 {
+            *counts
+                .entry(format!("{:?}", s.sample(&mut rng)))
+                .or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), s.count() as usize);
+        assert!(counts.values().all(|n| (60..=140).contains(n)));
+    }
+
+    #[test]
+    fn test_gen_spec() {
+        let mut rng = Generator::seeded_rng(9);
+        let cc = Generator::gen_spec_with_rng("solved: D-layer, E-slice edges", &mut rng).unwrap();
+        assert!(recognition::oll(&cc).is_ok());
+        assert!(Generator::gen_spec("solved: XY").is_err());
+    }
+}
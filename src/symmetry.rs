@@ -0,0 +1,201 @@
+//! # Symmetry
+//! The 48 symmetries of the cube: the 24 whole-cube rotations, each optionally followed by the mirror across the M plane.
+//! A symmetry `S` conjugates a cube state `A` to `S A S⁻¹`, i.e, the same state seen from another orientation
+//! (or in a mirror), so that `cube.apply_formula(f).conjugate(s) == cube.conjugate(s).apply_formula(&s.apply_formula(f))`.
+//! # Example
+//! ```rust
+//! use rcuber::cubie::CubieCube;
+//! use rcuber::moves::{Formula, Move};
+//! use rcuber::symmetry::Symmetry;
+//!
+//! fn main() {
+//!     let sune = Formula::from_string("R U R' U R U2 R'").unwrap();
+//!     let cc = CubieCube::default().apply_formula(&sune);
+//!     let y_sune = CubieCube::default().apply_formula(&sune.rotated_by(Move::y));
+//!     assert_eq!(cc.canonical(), y_sune.canonical());
+//!     assert_eq!(cc.self_symmetries().len(), 1);
+//!     assert_eq!(CubieCube::default().self_symmetries().len(), 48);
+//!     assert_eq!(Symmetry::all().len(), 48);
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::cubie::{CubieCube, SOLVED_CUBIE_CUBE};
+use crate::moves::{Formula, Move, Move::*, Plane};
+
+/// Number of the symmetries of the cube.
+pub const N_SYM: usize = 48;
+
+/// Rotations bringing each face to U.
+const FACE_TO_U: [&[Move]; 6] = [&[], &[z3], &[x], &[x2], &[z], &[x3]];
+
+/// A symmetry of the cube, `index` is `rotation * 2 + mirror` for the 24 rotations.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Symmetry {
+    index: u8,
+}
+
+impl Symmetry {
+    /// The symmetry of `index`(0..48), `None` if out of range.
+    pub fn new(index: usize) -> Option<Self> {
+        match index < N_SYM {
+            true => Some(Self { index: index as u8 }),
+            false => None,
+        }
+    }
+
+    /// The identity symmetry.
+    pub fn identity() -> Self {
+        Self { index: 0 }
+    }
+
+    /// All the 48 symmetries, the identity first.
+    pub fn all() -> Vec<Self> {
+        (0..N_SYM).map(|i| Self { index: i as u8 }).collect()
+    }
+
+    /// Index of the symmetry, in 0..48.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// Check if the symmetry is a reflection, i.e, it has a mirror.
+    pub fn is_mirror(&self) -> bool {
+        self.index % 2 == 1
+    }
+
+    /// The rotation of the symmetry, as at most two rotation moves.
+    pub fn rotation(&self) -> Vec<Move> {
+        let r = self.index as usize / 2;
+        let mut moves = FACE_TO_U[r / 4].to_vec();
+        match r % 4 {
+            0 => {}
+            i => moves.push([y, y2, y3][i - 1]),
+        }
+        moves
+    }
+
+    /// The inverse symmetry.
+    pub fn inverse(&self) -> Self {
+        // A cube without self-symmetries tells the symmetries apart.
+        let cc = SOLVED_CUBIE_CUBE.apply_moves(&[R, U2, F3, D, L2, B, U]);
+        let conjugated = cc.conjugate(*self);
+        Symmetry::all()
+            .into_iter()
+            .find(|s| conjugated.conjugate(*s) == cc)
+            .unwrap()
+    }
+
+    /// Conjugate a formula by the symmetry, i.e, the formula relabelled to the rotated (and mirrored) cube.
+    pub fn apply_formula(&self, formula: &Formula) -> Formula {
+        let mut f = formula.clone();
+        for r in self.rotation().into_iter().rev() {
+            f = f.rotated_by(r);
+        }
+        match self.is_mirror() {
+            true => f.mirror(Plane::M),
+            false => f,
+        }
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = Formula {
+            moves: self.rotation(),
+        };
+        match (self.is_mirror(), r.moves.is_empty()) {
+            (false, true) => write!(f, "I"),
+            (false, false) => write!(f, "{}", r),
+            (true, true) => write!(f, "M"),
+            (true, false) => write!(f, "{} M", r),
+        }
+    }
+}
+
+impl CubieCube {
+    /// Conjugate the cube by the symmetry `sym`.
+    pub fn conjugate(&self, sym: Symmetry) -> Self {
+        let rotation = sym.rotation();
+        let r = SOLVED_CUBIE_CUBE.apply_moves(&rotation);
+        let inverse: Vec<Move> = rotation.iter().rev().map(|m| m.get_inverse()).collect();
+        let cc = r * *self * SOLVED_CUBIE_CUBE.apply_moves(&inverse);
+        match sym.is_mirror() {
+            true => cc.mirror(Plane::M),
+            false => cc,
+        }
+    }
+
+    /// The symmetries leaving the cube unchanged, the identity included.
+    pub fn self_symmetries(&self) -> Vec<Symmetry> {
+        Symmetry::all()
+            .into_iter()
+            .filter(|s| self.conjugate(*s) == *self)
+            .collect()
+    }
+
+    /// The representative of the cube up to symmetry: the smallest of its 48 conjugates,
+    /// so the cubes equal up to symmetry have the same canonical cube.
+    pub fn canonical(&self) -> Self {
+        Symmetry::all()
+            .into_iter()
+            .map(|s| self.conjugate(s))
+            .min_by_key(sort_key)
+            .unwrap()
+    }
+}
+
+/// A total order of the cubes, by the permutations and orientations.
+fn sort_key(cc: &CubieCube) -> Vec<u8> {
+    let mut key: Vec<u8> = cc.cp.iter().map(|c| *c as u8).collect();
+    key.extend(cc.ep.iter().map(|e| *e as u8));
+    key.extend(cc.co);
+    key.extend(cc.eo);
+    key.extend(cc.center.iter().map(|c| *c as u8));
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symmetries() {
+        let f = Formula::from_string("R U2 F' D L2 B U Rw M' y").unwrap();
+        let cc = CubieCube::default().apply_formula(&f);
+        let scramble = CubieCube::default().apply_formula(&Formula::scramble());
+        let mut conjugates = Vec::new();
+        for s in Symmetry::all() {
+            let c = cc.conjugate(s);
+            assert_eq!(c, CubieCube::default().apply_formula(&s.apply_formula(&f)), "{}", s);
+            assert_eq!(
+                scramble.apply_formula(&f).conjugate(s),
+                scramble.conjugate(s).apply_formula(&s.apply_formula(&f))
+            );
+            assert_eq!(c.conjugate(s.inverse()), cc);
+            assert!(!conjugates.contains(&c));
+            conjugates.push(c);
+        }
+        assert_eq!(Symmetry::new(48), None);
+        assert_eq!(Symmetry::identity().to_string(), "I");
+        assert_eq!(Symmetry::new(3).unwrap().to_string(), "y M");
+    }
+
+    #[test]
+    fn test_canonical() {
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        for s in Symmetry::all() {
+            assert_eq!(cc.conjugate(s).canonical(), cc.canonical());
+        }
+        let superflip = Formula::from_string("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2").unwrap();
+        let superflip = CubieCube::default().apply_formula(&superflip);
+        assert_eq!(superflip.self_symmetries().len(), 48);
+        let checkerboard = Formula::from_string("M2 E2 S2").unwrap();
+        let checkerboard = CubieCube::default().apply_formula(&checkerboard);
+        assert_eq!(checkerboard.self_symmetries().len(), 48);
+        let t_perm = Formula::from_string("R U R' U' R' F R2 U' R' U' R U R' F'").unwrap();
+        let t_perm = CubieCube::default().apply_formula(&t_perm);
+        assert_eq!(t_perm.self_symmetries().len(), 2);
+    }
+}
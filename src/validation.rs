@@ -0,0 +1,504 @@
+//! # Validation
+//! Piece level diagnostics of invalid cube states, eg. a cube typed in wrong.
+//! `CubieCube::validate` and `FaceCube::validate` list the issues of the state, each with a suggested physical fix:
+//! * Facelets: wrong centers, wrong number of stickers of a colour, stickers which are no corner or edge.
+//! * Pieces: duplicated pieces (with their positions) and missing pieces.
+//! * Orientation and permutation: invalid twist or flip values, the total twist of the corners, the total flip of the edges and the parity.
+//!
+//! # Example
+//! ```rust
+//! use rcuber::cubie::CubieCube;
+//!
+//! fn main() {
+//!     let mut cc = CubieCube::default();
+//!     cc.co[0] = 1;
+//!     let report = cc.validate();
+//!     assert!(!report.is_valid());
+//!     assert_eq!(report.to_string(), "the total twist of the corners is 1: twist URF counter-clockwise\n");
+//!     assert!(CubieCube::default().validate().is_valid());
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::constants::{ALL_COLORS, ALL_CORNERS, ALL_EDGES};
+use crate::cubie::{Corner, CubieCube, Edge};
+use crate::facelet::{
+    Color, FaceCube, CENTER_FACELET, CORNER_COLOR, CORNER_FACELET, EDGE_COLOR, EDGE_FACELET,
+};
+
+/// An issue of an invalid cube state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The center of the `face` has the `color`.
+    Center { face: Color, color: Color },
+    /// There are `count` stickers of the `color`, instead of 9.
+    ColorCount { color: Color, count: usize },
+    /// The stickers at the corner `position` are no corner.
+    CornerStickers {
+        position: Corner,
+        colors: [Color; 3],
+    },
+    /// The stickers at the edge `position` are no edge.
+    EdgeStickers { position: Edge, colors: [Color; 2] },
+    /// The `corner` is at more than one of the `positions`.
+    DuplicateCorner {
+        corner: Corner,
+        positions: Vec<Corner>,
+    },
+    /// The `edge` is at more than one of the `positions`.
+    DuplicateEdge { edge: Edge, positions: Vec<Edge> },
+    /// The `corner` is at no position.
+    MissingCorner { corner: Corner },
+    /// The `edge` is at no position.
+    MissingEdge { edge: Edge },
+    /// The twist of the corner at the `position` is `value`, not 0, 1 or 2.
+    InvalidTwist { position: Corner, value: u8 },
+    /// The flip of the edge at the `position` is `value`, not 0 or 1.
+    InvalidFlip { position: Edge, value: u8 },
+    /// The total twist of the corners is `residue`(1 or 2) instead of 0, modulo 3.
+    Twist { residue: u8 },
+    /// The total flip of the edges is odd.
+    Flip,
+    /// The corner and edge permutations have different parities.
+    Parity,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Center { face, color } => {
+                write!(f, "the center of the {} face is {}", face, color)
+            }
+            Issue::ColorCount { color, count } => {
+                write!(f, "there are {} {} stickers", count, color)
+            }
+            Issue::CornerStickers { position, colors } => {
+                write!(
+                    f,
+                    "the stickers {}{}{} at {} are no corner",
+                    colors[0], colors[1], colors[2], position
+                )
+            }
+            Issue::EdgeStickers { position, colors } => {
+                write!(
+                    f,
+                    "the stickers {}{} at {} are no edge",
+                    colors[0], colors[1], position
+                )
+            }
+            Issue::DuplicateCorner { corner, positions } => {
+                write!(f, "the corner {} is at {}", corner, join(positions))
+            }
+            Issue::DuplicateEdge { edge, positions } => {
+                write!(f, "the edge {} is at {}", edge, join(positions))
+            }
+            Issue::MissingCorner { corner } => write!(f, "the corner {} is missing", corner),
+            Issue::MissingEdge { edge } => write!(f, "the edge {} is missing", edge),
+            Issue::InvalidTwist { position, value } => {
+                write!(f, "the twist of the corner at {} is {}", position, value)
+            }
+            Issue::InvalidFlip { position, value } => {
+                write!(f, "the flip of the edge at {} is {}", position, value)
+            }
+            Issue::Twist { residue } => write!(f, "the total twist of the corners is {}", residue),
+            Issue::Flip => write!(f, "the total flip of the edges is odd"),
+            Issue::Parity => write!(
+                f,
+                "the corner and edge permutations have different parities"
+            ),
+        }
+    }
+}
+
+/// An issue with a suggested physical fix, eg. "twist URF clockwise", "swap UF/UR".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub issue: Issue,
+    pub fix: String,
+}
+
+/// The diagnostics of a cube state, empty for a valid state.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// Check if the state has no issue.
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// The issues of the state.
+    pub fn issues(&self) -> Vec<&Issue> {
+        self.diagnostics.iter().map(|d| &d.issue).collect()
+    }
+
+    fn push(&mut self, issue: Issue, fix: String) {
+        self.diagnostics.push(Diagnostic { issue, fix });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for d in &self.diagnostics {
+            writeln!(f, "{}: {}", d.issue, d.fix)?;
+        }
+        Ok(())
+    }
+}
+
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    let s: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+    s.join(", ")
+}
+
+/// The positions of each piece, and the pieces at no position.
+fn positions_of<T: Copy + PartialEq>(
+    pieces: &[Option<T>],
+    all: &[T],
+) -> (Vec<(T, Vec<T>)>, Vec<T>) {
+    let mut duplicates = Vec::new();
+    let mut missing = Vec::new();
+    for p in all {
+        let positions: Vec<T> = (0..pieces.len())
+            .filter(|i| pieces[*i] == Some(*p))
+            .map(|i| all[i])
+            .collect();
+        match positions.len() {
+            0 => missing.push(*p),
+            1 => {}
+            _ => duplicates.push((*p, positions)),
+        }
+    }
+    (duplicates, missing)
+}
+
+/// Report the duplicated and missing pieces of an orbit, pairing the extra positions with the missing pieces.
+fn check_orbit<T: Copy + PartialEq + fmt::Display>(
+    report: &mut ValidationReport,
+    pieces: &[Option<T>],
+    all: &[T],
+    duplicate: impl Fn(T, Vec<T>) -> Issue,
+    missing: impl Fn(T) -> Issue,
+) -> bool {
+    let (duplicates, absent) = positions_of(pieces, all);
+    let complete = pieces.iter().all(|p| p.is_some());
+    let mut replacements = absent.iter();
+    for (piece, positions) in &duplicates {
+        let fixes: Vec<String> = positions[1..]
+            .iter()
+            .map(|pos| match replacements.next() {
+                Some(r) => format!("replace the {} at {} with {}", piece, pos, r),
+                None => format!("check the {} at {}", piece, pos),
+            })
+            .collect();
+        report.push(duplicate(*piece, positions.clone()), fixes.join(", "));
+    }
+    if complete {
+        for piece in &absent {
+            report.push(
+                missing(*piece),
+                format!("put {} in place of a duplicated piece", piece),
+            );
+        }
+    }
+    complete && duplicates.is_empty()
+}
+
+impl CubieCube {
+    /// Validate the cube, listing the duplicated and missing pieces, the twist, flip and parity errors with their fixes.
+    /// An odd permutation of the centers (eg. after an M move) goes with different corner and edge parities.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let corners: Vec<Option<Corner>> = self.cp.iter().map(|c| Some(*c)).collect();
+        let edges: Vec<Option<Edge>> = self.ep.iter().map(|e| Some(*e)).collect();
+        let corners_ok = check_orbit(
+            &mut report,
+            &corners,
+            &ALL_CORNERS,
+            |corner, positions| Issue::DuplicateCorner { corner, positions },
+            |corner| Issue::MissingCorner { corner },
+        );
+        let edges_ok = check_orbit(
+            &mut report,
+            &edges,
+            &ALL_EDGES,
+            |edge, positions| Issue::DuplicateEdge { edge, positions },
+            |edge| Issue::MissingEdge { edge },
+        );
+        // The total twist and flip are meaningless with invalid orientations.
+        let mut orientations_ok = true;
+        for (i, value) in self.co.iter().enumerate().filter(|(_, o)| **o > 2) {
+            let position = ALL_CORNERS[i];
+            report.push(
+                Issue::InvalidTwist {
+                    position,
+                    value: *value,
+                },
+                format!("check the orientation of {}", position),
+            );
+            orientations_ok = false;
+        }
+        for (i, value) in self.eo.iter().enumerate().filter(|(_, o)| **o > 1) {
+            let position = ALL_EDGES[i];
+            report.push(
+                Issue::InvalidFlip {
+                    position,
+                    value: *value,
+                },
+                format!("check the orientation of {}", position),
+            );
+            orientations_ok = false;
+        }
+        if !orientations_ok {
+            return report;
+        }
+
+        let residue = self.co.iter().sum::<u8>() % 3;
+        if residue != 0 {
+            // A single wrong corner is the most likely, a corner with the residue twist first.
+            let i = (0..8).find(|i| self.co[*i] == residue).unwrap_or(0);
+            let direction = match residue {
+                1 => "counter-clockwise",
+                _ => "clockwise",
+            };
+            report.push(
+                Issue::Twist { residue },
+                format!("twist {} {}", ALL_CORNERS[i], direction),
+            );
+        }
+        if self.eo.iter().sum::<u8>() % 2 != 0 {
+            let i = (0..12).find(|i| self.eo[*i] == 1).unwrap_or(0);
+            report.push(Issue::Flip, format!("flip {}", ALL_EDGES[i]));
+        }
+        if corners_ok
+            && edges_ok
+            && (self.corner_parity() == self.edge_parity()) != self.center_parity()
+        {
+            // Swap a 2-cycle if any, i.e, two pieces typed in each other's place.
+            let cycles = self.cycles();
+            let fix = match (
+                cycles.edges.iter().find(|c| c.pieces.len() == 2),
+                cycles.corners.iter().find(|c| c.pieces.len() == 2),
+            ) {
+                (Some(c), _) => format!("swap {}/{}", c.pieces[0], c.pieces[1]),
+                (None, Some(c)) => format!("swap {}/{}", c.pieces[0], c.pieces[1]),
+                (None, None) => format!("swap {}/{}", ALL_EDGES[0], ALL_EDGES[1]),
+            };
+            report.push(Issue::Parity, fix);
+        }
+        report
+    }
+}
+
+impl FaceCube {
+    /// Validate the facelets, listing the wrong centers and stickers, then the issues of the pieces (see `CubieCube::validate`).
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        for (i, face) in ALL_COLORS.iter().enumerate() {
+            let color = self.f[CENTER_FACELET[i] as usize];
+            if color != *face {
+                report.push(
+                    Issue::Center { face: *face, color },
+                    format!(
+                        "hold the cube with the {} center on the {} face",
+                        face, face
+                    ),
+                );
+            }
+        }
+        for color in ALL_COLORS {
+            let count = self.f.iter().filter(|c| **c == color).count();
+            if count != 9 {
+                report.push(
+                    Issue::ColorCount { color, count },
+                    format!("check the {} stickers", color),
+                );
+            }
+        }
+
+        let mut cc = CubieCube::default();
+        let mut corners = Vec::new();
+        for (i, facelets) in CORNER_FACELET.iter().enumerate() {
+            let colors = facelets.map(|f| self.f[f as usize]);
+            let piece = (0..3).find_map(|ori| {
+                (0..8)
+                    .find(|j| (0..3).all(|k| colors[(ori + k) % 3] == CORNER_COLOR[*j][k]))
+                    .map(|j| (j, ori))
+            });
+            match piece {
+                Some((j, ori)) => {
+                    cc.cp[i] = ALL_CORNERS[j];
+                    cc.co[i] = ori as u8;
+                    corners.push(Some(ALL_CORNERS[j]));
+                }
+                None => {
+                    let position = ALL_CORNERS[i];
+                    report.push(
+                        Issue::CornerStickers { position, colors },
+                        format!("check the stickers of {}", position),
+                    );
+                    corners.push(None);
+                }
+            }
+        }
+        let mut edges = Vec::new();
+        for (i, facelets) in EDGE_FACELET.iter().enumerate() {
+            let colors = facelets.map(|f| self.f[f as usize]);
+            let piece = (0..2).find_map(|ori| {
+                (0..12)
+                    .find(|j| (0..2).all(|k| colors[(ori + k) % 2] == EDGE_COLOR[*j][k]))
+                    .map(|j| (j, ori))
+            });
+            match piece {
+                Some((j, ori)) => {
+                    cc.ep[i] = ALL_EDGES[j];
+                    cc.eo[i] = ori as u8;
+                    edges.push(Some(ALL_EDGES[j]));
+                }
+                None => {
+                    let position = ALL_EDGES[i];
+                    report.push(
+                        Issue::EdgeStickers { position, colors },
+                        format!("check the stickers of {}", position),
+                    );
+                    edges.push(None);
+                }
+            }
+        }
+
+        if corners.iter().all(|c| c.is_some()) && edges.iter().all(|e| e.is_some()) {
+            report.diagnostics.extend(cc.validate().diagnostics);
+        } else {
+            check_orbit(
+                &mut report,
+                &corners,
+                &ALL_CORNERS,
+                |corner, positions| Issue::DuplicateCorner { corner, positions },
+                |corner| Issue::MissingCorner { corner },
+            );
+            check_orbit(
+                &mut report,
+                &edges,
+                &ALL_EDGES,
+                |edge, positions| Issue::DuplicateEdge { edge, positions },
+                |edge| Issue::MissingEdge { edge },
+            );
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moves::Formula;
+
+    #[test]
+    fn test_validate_cubie() {
+        let cc = CubieCube::default().apply_formula(&Formula::scramble());
+        assert!(cc.validate().is_valid());
+        assert!(cc.verify().is_ok());
+        let mslice = CubieCube::default().apply_formula(&Formula::from_string("M U").unwrap());
+        assert!(mslice.validate().is_valid());
+
+        let mut cc = CubieCube::default();
+        cc.co[4] = 2;
+        assert_eq!(cc.validate().issues(), [&Issue::Twist { residue: 2 }]);
+        assert_eq!(cc.validate().diagnostics[0].fix, "twist DFR clockwise");
+        assert!(matches!(cc.verify(), Err(crate::error::Error::TwistError)));
+
+        let mut cc = CubieCube::default();
+        cc.eo[1] = 1;
+        assert_eq!(
+            cc.validate().to_string(),
+            "the total flip of the edges is odd: flip UF\n"
+        );
+
+        let mut cc = CubieCube::default();
+        cc.ep.swap(0, 1);
+        assert_eq!(cc.validate().issues(), [&Issue::Parity]);
+        assert_eq!(cc.validate().diagnostics[0].fix, "swap UR/UF");
+        assert!(matches!(cc.verify(), Err(crate::error::Error::ParityError)));
+
+        let mut cc = CubieCube::default();
+        cc.cp[1] = Corner::URF;
+        let report = cc.validate();
+        assert_eq!(
+            report.issues(),
+            [
+                &Issue::DuplicateCorner {
+                    corner: Corner::URF,
+                    positions: vec![Corner::URF, Corner::UFL]
+                },
+                &Issue::MissingCorner {
+                    corner: Corner::UFL
+                }
+            ]
+        );
+        assert_eq!(report.diagnostics[0].fix, "replace the URF at UFL with UFL");
+
+        let mut cc = CubieCube::default();
+        cc.co[0] = 3;
+        cc.eo[4] = 2;
+        let report = cc.validate();
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.issues(),
+            [
+                &Issue::InvalidTwist {
+                    position: Corner::URF,
+                    value: 3
+                },
+                &Issue::InvalidFlip {
+                    position: Edge::DR,
+                    value: 2
+                }
+            ]
+        );
+        assert_eq!(
+            report.to_string(),
+            "the twist of the corner at URF is 3: check the orientation of URF\n\
+             the flip of the edge at DR is 2: check the orientation of DR\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_facelet() {
+        let fc = FaceCube::default();
+        assert!(fc.validate().is_valid());
+        let s = fc.to_string();
+
+        // Two stickers of an edge swapped, i.e, the edge flipped.
+        let mut chars: Vec<char> = s.chars().collect();
+        chars.swap(7, 19);
+        let fc = FaceCube::try_from(chars.iter().collect::<String>().as_str()).unwrap();
+        assert_eq!(fc.validate().issues(), [&Issue::Flip]);
+
+        // A sticker of the wrong colour.
+        let mut chars: Vec<char> = s.chars().collect();
+        chars[8] = 'D';
+        let fc = FaceCube::try_from(chars.iter().collect::<String>().as_str()).unwrap();
+        let report = fc.validate();
+        assert_eq!(
+            report.issues()[..3],
+            [
+                &Issue::ColorCount {
+                    color: Color::U,
+                    count: 8
+                },
+                &Issue::ColorCount {
+                    color: Color::D,
+                    count: 10
+                },
+                &Issue::CornerStickers {
+                    position: Corner::URF,
+                    colors: [Color::D, Color::R, Color::F]
+                }
+            ]
+        );
+        assert_eq!(report.diagnostics[2].fix, "check the stickers of URF");
+    }
+}